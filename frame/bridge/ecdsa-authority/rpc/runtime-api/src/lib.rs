@@ -0,0 +1,33 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition required by ecdsa-authority RPC extensions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use darwinia_ecdsa_authority::primitives::{Feed, SigningPayload, SigningProgress};
+
+sp_api::decl_runtime_apis! {
+	pub trait EcdsaAuthorityApi {
+		/// Get the pending authorities change and its signing progress.
+		fn pending_authorities_change() -> Option<SigningProgress>;
+
+		/// Get the pending new message root of the feed and its signing progress.
+		fn pending_new_message_root(feed: Feed) -> Option<SigningProgress>;
+	}
+}
@@ -0,0 +1,107 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC extensions for ecdsa-authority.
+
+pub use darwinia_ecdsa_authority_rpc_runtime_api::{
+	EcdsaAuthorityApi as EcdsaAuthorityRuntimeApi, Feed, SigningPayload, SigningProgress,
+};
+
+// --- core ---
+use core::{fmt::Debug, marker::PhantomData};
+// --- std ---
+use std::sync::Arc;
+// --- crates.io ---
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+// --- paritytech ---
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+const RUNTIME_ERROR: i64 = -1;
+
+#[rpc]
+pub trait EcdsaAuthorityApi<BlockHash> {
+	/// Get the pending authorities change, the message to sign and the collected signatures.
+	#[rpc(name = "ecdsaAuthority_pendingAuthoritiesChange")]
+	fn pending_authorities_change(&self, at: Option<BlockHash>) -> Result<Option<SigningProgress>>;
+
+	/// Get the pending new message root of the feed, the message to sign and the collected
+	/// signatures.
+	#[rpc(name = "ecdsaAuthority_pendingNewMessageRoot")]
+	fn pending_new_message_root(
+		&self,
+		feed: Feed,
+		at: Option<BlockHash>,
+	) -> Result<Option<SigningProgress>>;
+}
+
+pub struct EcdsaAuthority<Client, Block> {
+	client: Arc<Client>,
+	_marker: PhantomData<Block>,
+}
+impl<Client, Block> EcdsaAuthority<Client, Block> {
+	pub fn new(client: Arc<Client>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+impl<Client, Block> EcdsaAuthority<Client, Block>
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block>,
+{
+	fn at(&self, at: Option<Block::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+impl<Client, Block> EcdsaAuthorityApi<<Block as BlockT>::Hash> for EcdsaAuthority<Client, Block>
+where
+	Block: BlockT,
+	Client: 'static + Send + Sync + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	Client::Api: EcdsaAuthorityRuntimeApi<Block>,
+{
+	fn pending_authorities_change(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SigningProgress>> {
+		self.client
+			.runtime_api()
+			.pending_authorities_change(&self.at(at))
+			.map_err(|e| runtime_error("Failed to get the pending authorities change.", e))
+	}
+
+	fn pending_new_message_root(
+		&self,
+		feed: Feed,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SigningProgress>> {
+		self.client
+			.runtime_api()
+			.pending_new_message_root(&self.at(at), feed)
+			.map_err(|e| runtime_error("Failed to get the pending new message root.", e))
+	}
+}
+
+fn runtime_error(message: &str, e: impl Debug) -> Error {
+	Error {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: message.into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
@@ -36,21 +36,35 @@ pub use weights::WeightInfo;
 // --- crates.io ---
 use ethabi::Token;
 // --- paritytech ---
-use frame_support::{pallet_prelude::*, traits::Get};
-use frame_system::pallet_prelude::*;
+use frame_support::{
+	log,
+	pallet_prelude::*,
+	traits::{Get, StorageVersion},
+	weights::PostDispatchInfo,
+};
+use frame_system::{
+	offchain::{SendTransactionTypes, SubmitTransaction},
+	pallet_prelude::*,
+};
 use sp_runtime::{
 	traits::{SaturatedConversion, Saturating, Zero},
 	Perbill,
 };
 use sp_std::prelude::*;
 
+/// The storage version since the message roots became per feed.
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+/// The weight of a signature submission, excluding the finished signature rounds.
+const SUBMISSION_WEIGHT: Weight = 10_000_000;
+
 #[frame_support::pallet]
 pub mod pallet {
 	// --- darwinia-network ---
 	use crate::*;
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 		// Overrides.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		// Basics.
@@ -67,10 +81,11 @@ pub mod pallet {
 		/// Once `signatures_count / authorities_count >= threshold`, we say the message is trusted.
 		#[pallet::constant]
 		type SignThreshold: Get<Perbill>;
-		/// The Darwinia message root.
+		/// The Darwinia message roots of the feeds.
 		///
-		/// If it changed, it means there are some new messages which are waiting for relaying.
-		type MessageRoot: Get<Option<Hash>>;
+		/// If a feed's root changed, it means there are some new messages which are waiting for
+		/// relaying to the feed's target chain.
+		type MessageRoots: MessageRootFeeds;
 		// Checkpoints.
 		// `SyncInterval` must be shorter than `MaxPendingPeriod`.
 		/// The interval of checking the message root.
@@ -82,6 +97,22 @@ pub mod pallet {
 		/// `MaxPendingPeriod`, we will drop the root. And update the root with a new one.
 		#[pallet::constant]
 		type MaxPendingPeriod: Get<Self::BlockNumber>;
+		/// How long should we wait for the authorities change to be signed.
+		///
+		/// If the collecting authorities change signatures process takes more than
+		/// `MaxAuthoritiesChangePendingPeriod`, the change will be aborted.
+		#[pallet::constant]
+		type MaxAuthoritiesChangePendingPeriod: Get<Self::BlockNumber>;
+		// Participation relates.
+		/// Handler for the finished signature rounds.
+		type OnSignatureRound: OnSignatureRound<Self::BlockNumber>;
+		/// How many finished signature rounds should be kept in the storage.
+		#[pallet::constant]
+		type MaxSignatureRoundRecords: Get<u32>;
+		// Off-chain worker relates.
+		/// The priority of the unsigned signature submission.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
 	}
 
 	#[pallet::event]
@@ -96,10 +127,13 @@ pub mod pallet {
 			message: Message,
 			signatures: Vec<(Address, Signature)>,
 		},
+		/// The authorities change didn't collect enough signatures in time.
+		AuthoritiesChangeExpired { operation: Operation, message: Message },
 		/// New message root found. Collecting new message root signatures.
-		CollectingNewMessageRootSignatures { message: Message },
+		CollectingNewMessageRootSignatures { feed: Feed, message: Message },
 		/// Collected enough new message root signatures.
 		CollectedEnoughNewMessageRootSignatures {
+			feed: Feed,
 			commitment: Commitment,
 			message: Message,
 			signatures: Vec<(Address, Signature)>,
@@ -126,6 +160,10 @@ pub mod pallet {
 		BadSignature,
 		/// This authority had already finished his duty.
 		AlreadySubmitted,
+		/// The batch doesn't contain any authority change.
+		EmptyBatch,
+		/// Too many authority changes in a batch.
+		TooManyChanges,
 	}
 
 	/// The current active authorities.
@@ -141,6 +179,8 @@ pub mod pallet {
 		StorageValue<_, BoundedVec<Address, T::MaxAuthorities>, ValueQuery>;
 
 	/// The nonce of the current active authorities. AKA term/session/era.
+	///
+	/// All the feeds share this nonce, since they are signed by the same authorities.
 	#[pallet::storage]
 	#[pallet::getter(fn nonce)]
 	pub type Nonce<T: Config> = StorageValue<_, u32, ValueQuery>;
@@ -154,22 +194,43 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
-	/// The new message root waiting for signing.
+	/// The block number when the authorities change started collecting signatures.
+	#[pallet::storage]
+	#[pallet::getter(fn authorities_change_started_at)]
+	pub type AuthoritiesChangeStartedAt<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	/// The new message root waiting for signing of each feed.
 	#[pallet::storage]
 	#[pallet::getter(fn new_message_root_to_sign)]
-	pub type NewMessageRootToSign<T: Config> = StorageValue<
+	pub type NewMessageRootToSign<T: Config> = StorageMap<
 		_,
+		Twox64Concat,
+		Feed,
 		(Commitment, Message, BoundedVec<(Address, Signature), T::MaxAuthorities>),
 		OptionQuery,
 	>;
 
-	/// Record the previous message root.
+	/// Record the previous message root of each feed.
 	///
 	/// Use for checking if the message root getter get the same message root as the previous one.
 	/// And if this is empty, it means the message root is require to be relayed.
 	#[pallet::storage]
 	#[pallet::getter(fn previous_message_root)]
-	pub type PreviousMessageRoot<T: Config> = StorageValue<_, (T::BlockNumber, Hash), OptionQuery>;
+	pub type PreviousMessageRoot<T: Config> =
+		StorageMap<_, Twox64Concat, Feed, (T::BlockNumber, Hash), OptionQuery>;
+
+	/// The count of the finished signature rounds.
+	#[pallet::storage]
+	#[pallet::getter(fn signature_round_count)]
+	pub type SignatureRoundCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The participation records of the recent finished signature rounds.
+	///
+	/// Only the latest `MaxSignatureRoundRecords` records are kept.
+	#[pallet::storage]
+	#[pallet::getter(fn signature_round_of)]
+	pub type SignatureRounds<T: Config> =
+		StorageMap<_, Twox64Concat, u32, SignatureRound<T::BlockNumber>, OptionQuery>;
 
 	#[derive(Default)]
 	#[pallet::genesis_config]
@@ -185,18 +246,33 @@ pub mod pallet {
 	}
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
 		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut weight = Self::try_expire_authorities_change(now);
+
 			if (now % T::SyncInterval::get()).is_zero() {
-				if let Some(message_root) = Self::try_update_message_root(now, false) {
-					Self::on_new_message_root(now, message_root);
-				}
+				weight = weight.saturating_add(Self::try_update_message_roots(now, false));
 			}
 
-			10_000_000
+			weight
+		}
+
+		fn offchain_worker(_: T::BlockNumber) {
+			// Only works if the node holds the authority's key.
+			if let Some((_, _, message, collected)) = <AuthoritiesChangeToSign<T>>::get() {
+				Self::sign_and_submit(message, &collected, |address, signature| {
+					Call::submit_authorities_change_signature { address, signature }
+				});
+			}
+			for (feed, (_, message, collected)) in <NewMessageRootToSign<T>>::iter() {
+				Self::sign_and_submit(message, &collected, |address, signature| {
+					Call::submit_new_message_root_signature { feed, address, signature }
+				});
+			}
 		}
 	}
 	#[pallet::call]
@@ -212,17 +288,13 @@ pub mod pallet {
 
 			Self::ensure_not_on_authorities_change()?;
 
-			let authorities_count = <NextAuthorities<T>>::try_mutate(|authorities| {
-				if authorities.contains(&new) {
-					return Err(<Error<T>>::AuthorityExisted)?;
-				}
+			let (authorities_count, operation) = <NextAuthorities<T>>::try_mutate(|authorities| {
+				let operation = Self::add_member(authorities, new)?;
 
-				authorities.try_insert(0, new).map_err(|_| <Error<T>>::TooManyAuthorities)?;
-
-				Ok::<_, DispatchError>(authorities.len() as u32)
+				Ok::<_, DispatchError>((authorities.len() as u32, operation))
 			})?;
 
-			Self::on_authorities_change(Operation::AddMember { new }, authorities_count);
+			Self::on_authorities_change(operation, authorities_count);
 
 			Ok(())
 		}
@@ -237,23 +309,13 @@ pub mod pallet {
 
 			Self::ensure_not_on_authorities_change()?;
 
-			let (authorities_count, pre) = <NextAuthorities<T>>::try_mutate(|authorities| {
-				let i =
-					authorities.iter().position(|a| a == &old).ok_or(<Error<T>>::NotAuthority)?;
-
-				if authorities.len() == 1 {
-					return Err(<Error<T>>::AtLeastOneAuthority)?;
-				}
-
-				authorities.remove(i);
+			let (authorities_count, operation) = <NextAuthorities<T>>::try_mutate(|authorities| {
+				let operation = Self::remove_member(authorities, old)?;
 
-				Ok::<_, DispatchError>((
-					authorities.len() as u32,
-					if i == 0 { AUTHORITY_SENTINEL } else { authorities[i - 1] },
-				))
+				Ok::<_, DispatchError>((authorities.len() as u32, operation))
 			})?;
 
-			Self::on_authorities_change(Operation::RemoveMember { pre, old }, authorities_count);
+			Self::on_authorities_change(operation, authorities_count);
 
 			Ok(())
 		}
@@ -268,22 +330,51 @@ pub mod pallet {
 
 			Self::ensure_not_on_authorities_change()?;
 
-			let (authorities_count, pre) = <NextAuthorities<T>>::try_mutate(|authorities| {
-				let i =
-					authorities.iter().position(|a| a == &old).ok_or(<Error<T>>::NotAuthority)?;
-
-				authorities[i] = new;
+			let (authorities_count, operation) = <NextAuthorities<T>>::try_mutate(|authorities| {
+				let operation = Self::swap_members(authorities, old, new)?;
 
-				Ok::<_, DispatchError>((
-					authorities.len() as u32,
-					if i == 0 { AUTHORITY_SENTINEL } else { authorities[i - 1] },
-				))
+				Ok::<_, DispatchError>((authorities.len() as u32, operation))
 			})?;
 
-			Self::on_authorities_change(
-				Operation::SwapMembers { pre, old, new },
-				authorities_count,
-			);
+			Self::on_authorities_change(operation, authorities_count);
+
+			Ok(())
+		}
+
+		/// Apply a batch of authority changes in order and trigger `on_authorities_change` once.
+		///
+		/// Not allow to call while authorities is changing.
+		/// The whole batch only requires one signature round.
+		#[pallet::weight(10_000_000)]
+		#[frame_support::transactional]
+		pub fn batch_change_authorities(
+			origin: OriginFor<T>,
+			changes: Vec<AuthorityChange>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Self::ensure_not_on_authorities_change()?;
+
+			ensure!(!changes.is_empty(), <Error<T>>::EmptyBatch);
+			ensure!(changes.len() as u32 <= T::MaxAuthorities::get(), <Error<T>>::TooManyChanges);
+
+			let (authorities_count, operations) =
+				<NextAuthorities<T>>::try_mutate(|authorities| {
+					let operations = changes
+						.into_iter()
+						.map(|change| match change {
+							AuthorityChange::Add { new } => Self::add_member(authorities, new),
+							AuthorityChange::Remove { old } =>
+								Self::remove_member(authorities, old),
+							AuthorityChange::Swap { old, new } =>
+								Self::swap_members(authorities, old, new),
+						})
+						.collect::<Result<Vec<_>, _>>()?;
+
+					Ok::<_, DispatchError>((authorities.len() as u32, operations))
+				})?;
+
+			Self::on_authorities_change(Operation::BatchMembers { operations }, authorities_count);
 
 			Ok(())
 		}
@@ -291,14 +382,21 @@ pub mod pallet {
 		/// Submit the authorities change signature.
 		///
 		/// Free to submit the first-correct signature.
-		#[pallet::weight(10_000_000)]
+		/// Also accept the unsigned submission from the off-chain worker.
+		#[pallet::weight(
+			// Finishing the pending new message roots and the authorities change.
+			SUBMISSION_WEIGHT.saturating_add(
+				<Pallet<T>>::max_finish_signature_round_weight()
+					.saturating_mul(T::MessageRoots::feeds().len() as Weight + 1)
+			)
+		)]
 		#[frame_support::transactional]
 		pub fn submit_authorities_change_signature(
 			origin: OriginFor<T>,
 			address: Address,
 			signature: Signature,
 		) -> DispatchResultWithPostInfo {
-			ensure_signed(origin)?;
+			Self::ensure_signed_or_none(origin)?;
 
 			let authorities = Self::ensure_authority(&address)?;
 			let mut authorities_change_to_sign =
@@ -314,7 +412,26 @@ pub mod pallet {
 
 			collected.try_push((address, signature)).map_err(|_| <Error<T>>::TooManyAuthorities)?;
 
+			let mut weight = SUBMISSION_WEIGHT;
+
 			if Self::check_threshold(collected.len() as _, authorities.len() as _) {
+				// The pending new message roots belong to the previous authorities.
+				// They will be replaced by the new ones after applying the next authorities.
+				for (feed, (_, message, collected)) in <NewMessageRootToSign<T>>::drain() {
+					weight = weight.saturating_add(Self::finish_signature_round(
+						RoundKind::NewMessageRoot { feed },
+						message,
+						&collected,
+						RoundOutcome::Expired,
+					));
+				}
+
+				weight = weight.saturating_add(Self::finish_signature_round(
+					RoundKind::AuthoritiesChange,
+					*message,
+					collected,
+					RoundOutcome::Completed,
+				));
 				Self::apply_next_authorities();
 
 				let (operation, new_threshold, message, collected) = authorities_change_to_sign;
@@ -326,33 +443,34 @@ pub mod pallet {
 					signatures: collected.to_vec(),
 				});
 
-				let now = <frame_system::Pallet<T>>::block_number();
-
-				if let Some(message_root) = Self::try_update_message_root(now, true) {
-					Self::on_new_message_root(now, message_root);
-				}
+				let _ =
+					Self::try_update_message_roots(<frame_system::Pallet<T>>::block_number(), true);
 			} else {
 				<AuthoritiesChangeToSign<T>>::put(authorities_change_to_sign);
 			}
 
-			Ok(Pays::No.into())
+			Ok(PostDispatchInfo { actual_weight: Some(weight), pays_fee: Pays::No })
 		}
 
 		/// Submit the new message root signature.
 		///
 		/// Free to submit the first-correct signature.
-		#[pallet::weight(10_000_000)]
+		/// Also accept the unsigned submission from the off-chain worker.
+		#[pallet::weight(
+			SUBMISSION_WEIGHT.saturating_add(<Pallet<T>>::max_finish_signature_round_weight())
+		)]
 		#[frame_support::transactional]
 		pub fn submit_new_message_root_signature(
 			origin: OriginFor<T>,
+			feed: Feed,
 			address: Address,
 			signature: Signature,
 		) -> DispatchResultWithPostInfo {
-			ensure_signed(origin)?;
+			Self::ensure_signed_or_none(origin)?;
 
 			let authorities = Self::ensure_authority(&address)?;
 			let mut new_message_root_to_sign =
-				<NewMessageRootToSign<T>>::get().ok_or(<Error<T>>::NoNewMessageRoot)?;
+				<NewMessageRootToSign<T>>::get(feed).ok_or(<Error<T>>::NoNewMessageRoot)?;
 			let (_, message, collected) = &mut new_message_root_to_sign;
 
 			Self::ensure_not_submitted(&address, collected)?;
@@ -364,24 +482,85 @@ pub mod pallet {
 
 			collected.try_push((address, signature)).map_err(|_| <Error<T>>::TooManyAuthorities)?;
 
+			let mut weight = SUBMISSION_WEIGHT;
+
 			if Self::check_threshold(collected.len() as _, authorities.len() as _) {
-				<NewMessageRootToSign<T>>::kill();
+				<NewMessageRootToSign<T>>::remove(feed);
+
+				weight = weight.saturating_add(Self::finish_signature_round(
+					RoundKind::NewMessageRoot { feed },
+					*message,
+					collected,
+					RoundOutcome::Completed,
+				));
 
 				let (commitment, message, collected) = new_message_root_to_sign;
 
 				Self::deposit_event(Event::<T>::CollectedEnoughNewMessageRootSignatures {
+					feed,
 					commitment,
 					message,
 					signatures: collected.to_vec(),
 				});
 			} else {
-				<NewMessageRootToSign<T>>::put(new_message_root_to_sign);
+				<NewMessageRootToSign<T>>::insert(feed, new_message_root_to_sign);
+			}
+
+			Ok(PostDispatchInfo { actual_weight: Some(weight), pays_fee: Pays::No })
+		}
+	}
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (feed, address, signature, to_sign) = match call {
+				Call::submit_authorities_change_signature { address, signature } => (
+					None,
+					address,
+					signature,
+					<AuthoritiesChangeToSign<T>>::get()
+						.map(|(_, _, message, collected)| (message, collected)),
+				),
+				Call::submit_new_message_root_signature { feed, address, signature } => (
+					Some(feed),
+					address,
+					signature,
+					<NewMessageRootToSign<T>>::get(feed)
+						.map(|(_, message, collected)| (message, collected)),
+				),
+				_ => return InvalidTransaction::Call.into(),
+			};
+			let (message, collected) = to_sign.ok_or(InvalidTransaction::Stale)?;
+
+			if Self::ensure_authority(address).is_err() {
+				return InvalidTransaction::BadSigner.into();
+			}
+			if Self::ensure_not_submitted(address, &collected).is_err() {
+				return InvalidTransaction::Stale.into();
+			}
+			if !Sign::verify_signature(signature, &message, address) {
+				return InvalidTransaction::BadProof.into();
 			}
 
-			Ok(Pays::No.into())
+			ValidTransaction::with_tag_prefix("EcdsaAuthority")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((feed, message, address))
+				.longevity(T::SyncInterval::get().saturated_into())
+				.propagate(true)
+				.build()
 		}
 	}
 	impl<T: Config> Pallet<T> {
+		fn ensure_signed_or_none(origin: OriginFor<T>) -> DispatchResult {
+			// The unsigned submission had already been verified by the `validate_unsigned`.
+			if ensure_none(origin.clone()).is_err() {
+				ensure_signed(origin)?;
+			}
+
+			Ok(())
+		}
+
 		fn ensure_authority(
 			address: &Address,
 		) -> Result<BoundedVec<Address, T::MaxAuthorities>, DispatchError> {
@@ -407,53 +586,115 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Get the pending authorities change and its signing progress.
+		pub fn pending_authorities_change() -> Option<SigningProgress> {
+			let (operation, new_threshold, message, collected) =
+				<AuthoritiesChangeToSign<T>>::get()?;
+
+			Some(Self::signing_progress(
+				SigningPayload::AuthoritiesChange { operation, new_threshold },
+				message,
+				collected.into_inner(),
+			))
+		}
+
+		/// Get the pending new message root of the feed and its signing progress.
+		pub fn pending_new_message_root(feed: Feed) -> Option<SigningProgress> {
+			let (commitment, message, collected) = <NewMessageRootToSign<T>>::get(feed)?;
+
+			Some(Self::signing_progress(
+				SigningPayload::NewMessageRoot { feed, commitment },
+				message,
+				collected.into_inner(),
+			))
+		}
+
+		fn signing_progress(
+			payload: SigningPayload,
+			message: Message,
+			signatures: Vec<(Address, Signature)>,
+		) -> SigningProgress {
+			let authorities_count = <Authorities<T>>::decode_len().unwrap_or_default() as u32;
+
+			SigningProgress {
+				payload,
+				message,
+				signatures,
+				threshold: Self::calculate_threshold(authorities_count),
+				authorities_count,
+			}
+		}
+
 		pub(crate) fn calculate_threshold(x: u32) -> u32 {
 			T::SignThreshold::get().mul_ceil(x)
 		}
 
+		fn add_member(
+			authorities: &mut BoundedVec<Address, T::MaxAuthorities>,
+			new: Address,
+		) -> Result<Operation, DispatchError> {
+			if authorities.contains(&new) {
+				return Err(<Error<T>>::AuthorityExisted)?;
+			}
+
+			authorities.try_insert(0, new).map_err(|_| <Error<T>>::TooManyAuthorities)?;
+
+			Ok(Operation::AddMember { new })
+		}
+
+		fn remove_member(
+			authorities: &mut BoundedVec<Address, T::MaxAuthorities>,
+			old: Address,
+		) -> Result<Operation, DispatchError> {
+			let i = authorities.iter().position(|a| a == &old).ok_or(<Error<T>>::NotAuthority)?;
+
+			if authorities.len() == 1 {
+				return Err(<Error<T>>::AtLeastOneAuthority)?;
+			}
+
+			authorities.remove(i);
+
+			Ok(Operation::RemoveMember {
+				pre: if i == 0 { AUTHORITY_SENTINEL } else { authorities[i - 1] },
+				old,
+			})
+		}
+
+		fn swap_members(
+			authorities: &mut BoundedVec<Address, T::MaxAuthorities>,
+			old: Address,
+			new: Address,
+		) -> Result<Operation, DispatchError> {
+			let i = authorities.iter().position(|a| a == &old).ok_or(<Error<T>>::NotAuthority)?;
+
+			authorities[i] = new;
+
+			Ok(Operation::SwapMembers {
+				pre: if i == 0 { AUTHORITY_SENTINEL } else { authorities[i - 1] },
+				old,
+				new,
+			})
+		}
+
 		fn on_authorities_change(operation: Operation, authorities_count: u32) {
-			let (authorities_changes, new_threshold) = {
-				match operation {
-					Operation::AddMember { new } => {
-						let new_threshold = Self::calculate_threshold(authorities_count);
-
-						(
-							ethabi::encode(&[
-								Token::Address(new),
-								Token::Uint(new_threshold.into()),
-							]),
-							Some(new_threshold),
-						)
-					},
-					Operation::RemoveMember { pre, old } => {
-						let new_threshold = Self::calculate_threshold(authorities_count);
-
-						(
-							ethabi::encode(&[
-								Token::Address(pre),
-								Token::Address(old),
-								Token::Uint(new_threshold.into()),
-							]),
-							Some(new_threshold),
-						)
-					},
-					Operation::SwapMembers { pre, old, new } => (
-						ethabi::encode(&[
-							Token::Address(pre),
-							Token::Address(old),
-							Token::Address(new),
-						]),
-						None,
-					),
-				}
+			let new_threshold = match operation {
+				// Swapping doesn't change the authorities count.
+				Operation::SwapMembers { .. } => None,
+				_ => Some(Self::calculate_threshold(authorities_count)),
 			};
+			let mut authorities_changes = operation.params();
+
+			if let Some(new_threshold) = new_threshold {
+				authorities_changes.push(Token::Uint(new_threshold.into()));
+			}
+
 			let message = Sign::eth_signable_message(
 				T::ChainId::get(),
 				T::Version::get().spec_name.as_ref(),
 				&ethabi::encode(&[
 					Token::FixedBytes(RELAY_TYPE_HASH.as_ref().into()),
 					Token::FixedBytes(operation.id().into()),
-					Token::Bytes(authorities_changes),
+					Token::Bytes(ethabi::encode(&authorities_changes)),
 					Token::Uint(<Nonce<T>>::get().into()),
 				]),
 			);
@@ -464,6 +705,7 @@ pub mod pallet {
 				message,
 				BoundedVec::default(),
 			));
+			<AuthoritiesChangeStartedAt<T>>::put(<frame_system::Pallet<T>>::block_number());
 
 			Self::deposit_event(Event::<T>::CollectingAuthoritiesChangeSignatures { message });
 		}
@@ -474,19 +716,181 @@ pub mod pallet {
 
 		pub(crate) fn apply_next_authorities() {
 			<AuthoritiesChangeToSign<T>>::kill();
+			<AuthoritiesChangeStartedAt<T>>::kill();
 			<Authorities<T>>::put(<NextAuthorities<T>>::get());
 			<Nonce<T>>::mutate(|nonce| *nonce += 1);
 		}
 
-		fn try_update_message_root(at: T::BlockNumber, force: bool) -> Option<Hash> {
+		fn try_expire_authorities_change(at: T::BlockNumber) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let started_at = match <AuthoritiesChangeStartedAt<T>>::get() {
+				Some(started_at) => started_at,
+				None => return db_weight.reads(1),
+			};
+
+			if at.saturating_sub(started_at) <= T::MaxAuthoritiesChangePendingPeriod::get() {
+				return db_weight.reads(1);
+			}
+
+			let (operation, _, message, collected) = match <AuthoritiesChangeToSign<T>>::get() {
+				Some(authorities_change_to_sign) => authorities_change_to_sign,
+				None => {
+					<AuthoritiesChangeStartedAt<T>>::kill();
+
+					return db_weight.reads_writes(2, 1);
+				},
+			};
+			let round_weight = Self::finish_signature_round(
+				RoundKind::AuthoritiesChange,
+				message,
+				&collected,
+				RoundOutcome::Expired,
+			);
+
+			<AuthoritiesChangeToSign<T>>::kill();
+			<AuthoritiesChangeStartedAt<T>>::kill();
+			<NextAuthorities<T>>::put(<Authorities<T>>::get());
+
+			Self::deposit_event(Event::<T>::AuthoritiesChangeExpired { operation, message });
+
+			db_weight.reads_writes(3, 4).saturating_add(round_weight)
+		}
+
+		/// The weight of `finish_signature_round`, excluding the `OnSignatureRound` handler.
+		fn finish_signature_round_weight() -> Weight {
+			T::DbWeight::get().reads_writes(2, 3)
+		}
+
+		/// The worst case weight of `finish_signature_round`.
+		pub(crate) fn max_finish_signature_round_weight() -> Weight {
+			Self::finish_signature_round_weight().saturating_add(T::OnSignatureRound::max_weight())
+		}
+
+		/// Record the finished round and notify the `OnSignatureRound` handler, return the
+		/// consumed weight.
+		fn finish_signature_round(
+			kind: RoundKind,
+			message: Message,
+			collected: &[(Address, Signature)],
+			outcome: RoundOutcome,
+		) -> Weight {
+			let signers = collected.iter().map(|(a, _)| *a).collect::<Vec<_>>();
+			let absentees = <Authorities<T>>::get()
+				.into_iter()
+				.filter(|a| !signers.contains(a))
+				.collect::<Vec<_>>();
+			let round = SignatureRound {
+				kind,
+				message,
+				finished_at: <frame_system::Pallet<T>>::block_number(),
+				outcome,
+				signers,
+				absentees,
+			};
+			let index = <SignatureRoundCount<T>>::mutate(|count| {
+				let index = *count;
+
+				*count = count.wrapping_add(1);
+
+				index
+			});
+
+			if let Some(stale) = index.checked_sub(T::MaxSignatureRoundRecords::get()) {
+				<SignatureRounds<T>>::remove(stale);
+			}
+
+			let handler_weight = T::OnSignatureRound::on_signature_round(&round);
+
+			<SignatureRounds<T>>::insert(index, round);
+
+			Self::finish_signature_round_weight().saturating_add(handler_weight)
+		}
+
+		fn sign_and_submit<F>(message: Message, collected: &[(Address, Signature)], to_call: F)
+		where
+			F: Fn(Address, Signature) -> Call<T>,
+		{
+			let authorities = <Authorities<T>>::get();
+
+			for (address, signature) in Sign::sign_with_local_keys(&message) {
+				if !authorities.contains(&address) || collected.iter().any(|(a, _)| a == &address) {
+					continue;
+				}
+
+				if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(
+					to_call(address, signature).into(),
+				)
+				.is_err()
+				{
+					log::error!(
+						target: "runtime::ecdsa-authority",
+						"Failed to submit the signature of {:?}",
+						address
+					);
+				}
+			}
+		}
+
+		fn commitment_message(commitment: &Commitment) -> Message {
+			Sign::eth_signable_message(
+				T::ChainId::get(),
+				T::Version::get().spec_name.as_ref(),
+				&ethabi::encode(&[
+					Token::FixedBytes(COMMIT_TYPE_HASH.as_ref().into()),
+					Token::Uint(commitment.block_number.into()),
+					Token::FixedBytes(commitment.message_root.as_ref().into()),
+					Token::Uint(commitment.nonce.into()),
+				]),
+			)
+		}
+
+		/// Verify the commitment signatures against the current authorities.
+		///
+		/// Return `true` if the commitment is under the current nonce and the distinct valid
+		/// signatures of the authorities reach the `SignThreshold`.
+		pub fn verify_commitment(
+			commitment: &Commitment,
+			signatures: &[(Address, Signature)],
+		) -> bool {
+			if commitment.nonce != <Nonce<T>>::get() {
+				return false;
+			}
+
+			<Self as AuthoritiesVerifier>::verify_signatures(
+				&Self::commitment_message(commitment),
+				signatures,
+			)
+		}
+
+		fn try_update_message_roots(at: T::BlockNumber, force: bool) -> Weight {
+			let db_weight = T::DbWeight::get();
+
 			// Not allow to relay the messages if the new authorities set is not verified.
 			if Self::ensure_not_on_authorities_change().is_err() {
-				return None;
+				return db_weight.reads(1);
+			}
+
+			let mut weight = db_weight.reads(1);
+
+			for feed in T::MessageRoots::feeds() {
+				// Reading the message root and reading/writing the previous message root.
+				weight = weight
+					.saturating_add(db_weight.reads_writes(2, 1))
+					.saturating_add(T::MessageRoots::message_root_weight(&feed));
+
+				if let Some(message_root) = Self::try_update_message_root(feed, at, force) {
+					weight =
+						weight.saturating_add(Self::on_new_message_root(feed, at, message_root));
+				}
 			}
 
-			let message_root = T::MessageRoot::get()?;
+			weight
+		}
+
+		fn try_update_message_root(feed: Feed, at: T::BlockNumber, force: bool) -> Option<Hash> {
+			let message_root = T::MessageRoots::message_root(&feed)?;
 
-			<PreviousMessageRoot<T>>::try_mutate(|maybe_previous_message_root| {
+			<PreviousMessageRoot<T>>::try_mutate(feed, |maybe_previous_message_root| {
 				if force {
 					*maybe_previous_message_root = Some((at, message_root));
 
@@ -519,27 +923,142 @@ pub mod pallet {
 			.ok()
 		}
 
-		fn on_new_message_root(at: T::BlockNumber, message_root: Hash) {
+		fn on_new_message_root(feed: Feed, at: T::BlockNumber, message_root: Hash) -> Weight {
+			// Reading the nonce and the unfinished round, writing the new round.
+			let mut weight = T::DbWeight::get().reads_writes(2, 1);
+
+			// Exceeded the max pending period, drop the unfinished round.
+			if let Some((_, message, collected)) = <NewMessageRootToSign<T>>::get(feed) {
+				weight = weight.saturating_add(Self::finish_signature_round(
+					RoundKind::NewMessageRoot { feed },
+					message,
+					&collected,
+					RoundOutcome::Expired,
+				));
+			}
+
 			let commitment = Commitment {
 				block_number: at.saturated_into::<u32>(),
 				message_root,
 				nonce: <Nonce<T>>::get(),
 			};
-			let message = Sign::eth_signable_message(
+			let message = Self::commitment_message(&commitment);
+
+			<NewMessageRootToSign<T>>::insert(feed, (commitment, message, BoundedVec::default()));
+
+			Self::deposit_event(Event::<T>::CollectingNewMessageRootSignatures { feed, message });
+
+			weight
+		}
+	}
+	impl<T: Config> AuthoritiesVerifier for Pallet<T> {
+		fn signable_message(data: &[u8]) -> Message {
+			Sign::eth_signable_message(
 				T::ChainId::get(),
 				T::Version::get().spec_name.as_ref(),
-				&ethabi::encode(&[
-					Token::FixedBytes(COMMIT_TYPE_HASH.as_ref().into()),
-					Token::Uint(commitment.block_number.into()),
-					Token::FixedBytes(commitment.message_root.as_ref().into()),
-					Token::Uint(commitment.nonce.into()),
-				]),
-			);
+				data,
+			)
+		}
+
+		fn verify_signatures(message: &Message, signatures: &[(Address, Signature)]) -> bool {
+			let authorities = <Authorities<T>>::get();
 
-			<NewMessageRootToSign<T>>::put((commitment, message, BoundedVec::default()));
+			if authorities.is_empty() {
+				return false;
+			}
 
-			Self::deposit_event(Event::<T>::CollectingNewMessageRootSignatures { message });
+			let mut signers = Vec::new();
+
+			for (address, signature) in signatures {
+				if authorities.contains(address)
+					&& !signers.contains(address)
+					&& Sign::verify_signature(signature, message, address)
+				{
+					signers.push(*address);
+				}
+			}
+
+			Self::check_threshold(signers.len() as _, authorities.len() as _)
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn max_authorities() -> u32 {
+			T::MaxAuthorities::get()
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn set_authorities(authorities: Vec<Address>) {
+			<Authorities<T>>::put(
+				BoundedVec::try_from(authorities)
+					.expect("The benchmarks must not exceed the `max_authorities`"),
+			);
 		}
 	}
 }
 pub use pallet::*;
+
+#[doc(hidden)]
+pub mod migration {
+	// --- paritytech ---
+	use frame_support::{migration, traits::PalletInfoAccess};
+	// --- darwinia-network ---
+	use crate::*;
+
+	#[cfg(feature = "try-runtime")]
+	pub mod try_runtime {
+		// --- darwinia-network ---
+		use crate::*;
+
+		pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+			if T::MessageRoots::feeds().is_empty() {
+				Err("No feed to migrate the message roots to.")
+			} else {
+				Ok(())
+			}
+		}
+	}
+
+	/// Move the single `NewMessageRootToSign` and `PreviousMessageRoot` under the first feed of
+	/// `Config::MessageRoots`, which is the only feed of a `SingleFeed` runtime.
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let module = <Pallet<T>>::name().as_bytes();
+		let feed = T::MessageRoots::feeds().first().copied();
+		let new_message_root_to_sign = migration::take_storage_value::<(
+			Commitment,
+			Message,
+			BoundedVec<(Address, Signature), T::MaxAuthorities>,
+		)>(module, b"NewMessageRootToSign", &[]);
+		let previous_message_root = migration::take_storage_value::<(T::BlockNumber, Hash)>(
+			module,
+			b"PreviousMessageRoot",
+			&[],
+		);
+		let mut writes = 3;
+
+		if let Some(feed) = feed {
+			if let Some(new_message_root_to_sign) = new_message_root_to_sign {
+				<NewMessageRootToSign<T>>::insert(feed, new_message_root_to_sign);
+
+				writes += 1;
+			}
+			if let Some(previous_message_root) = previous_message_root {
+				<PreviousMessageRoot<T>>::insert(feed, previous_message_root);
+
+				writes += 1;
+			}
+		} else {
+			log::warn!(
+				target: "runtime::ecdsa-authority",
+				"No feed to migrate the message roots to, dropped them"
+			);
+		}
+
+		STORAGE_VERSION.put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(3, writes)
+	}
+}
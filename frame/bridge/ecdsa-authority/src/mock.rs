@@ -22,11 +22,12 @@ use core::iter;
 use libsecp256k1::{PublicKey, SecretKey};
 // --- paritytech ---
 use frame_support::traits::{ConstU32, Everything, GenesisBuild, OnInitialize};
-use frame_system::mocking::*;
+use frame_system::{mocking::*, offchain::SendTransactionTypes};
 use sp_io::{hashing, TestExternalities};
 use sp_runtime::{
-	testing::Header,
+	testing::{Header, TestXt},
 	traits::{BlakeTwo256, IdentityLookup},
+	transaction_validity::TransactionPriority,
 	RuntimeString,
 };
 use sp_version::RuntimeVersion;
@@ -37,6 +38,7 @@ pub(crate) type EcdsaAuthorityError = Error<Test>;
 
 type Block = MockBlock<Test>;
 type UncheckedExtrinsic = MockUncheckedExtrinsic<Test>;
+pub(crate) type Extrinsic = TestXt<Call, ()>;
 
 type BlockNumber = u64;
 type AccountId = u64;
@@ -72,22 +74,72 @@ impl frame_system::Config for Test {
 	type Version = Version;
 }
 
+impl<C> SendTransactionTypes<C> for Test
+where
+	Call: From<C>,
+{
+	type Extrinsic = Extrinsic;
+	type OverarchingCall = Call;
+}
+
 frame_support::parameter_types! {
 	pub const ChainId: &'static [u8] = b"46";
 	pub const MaxAuthorities: u32 = 3;
+	pub const MaxAuthoritiesChangePendingPeriod: BlockNumber = 8;
 	pub const MaxPendingPeriod: BlockNumber = 5;
+	pub const MaxSignatureRoundRecords: u32 = 3;
 	pub const SignThreshold: Perbill = Perbill::from_percent(60);
 	pub const SyncInterval: BlockNumber = 3;
+	pub const UnsignedPriority: TransactionPriority = TransactionPriority::max_value();
 	pub static MessageRoot: Option<Hash> = Some(Default::default());
+	pub static OtherFeeds: Vec<(Feed, Hash)> = Vec::new();
+	pub static FinishedSignatureRounds: Vec<SignatureRound<BlockNumber>> = Vec::new();
+}
+pub struct SignatureRoundRecorder;
+impl OnSignatureRound<BlockNumber> for SignatureRoundRecorder {
+	fn on_signature_round(round: &SignatureRound<BlockNumber>) -> Weight {
+		FINISHED_SIGNATURE_ROUNDS.with(|v| v.borrow_mut().push(round.clone()));
+
+		SIGNATURE_ROUND_WEIGHT
+	}
+
+	fn max_weight() -> Weight {
+		SIGNATURE_ROUND_WEIGHT
+	}
+}
+pub(crate) const SIGNATURE_ROUND_WEIGHT: Weight = 1_000;
+pub(crate) const MESSAGE_ROOT_WEIGHT: Weight = 100;
+pub(crate) const FEED: Feed = Feed { target_chain_id: 1, source: [0; 4] };
+pub struct MockMessageRoots;
+impl MessageRootFeeds for MockMessageRoots {
+	fn feeds() -> Vec<Feed> {
+		iter::once(FEED).chain(OtherFeeds::get().into_iter().map(|(feed, _)| feed)).collect()
+	}
+
+	fn message_root(feed: &Feed) -> Option<Hash> {
+		if feed == &FEED {
+			MessageRoot::get()
+		} else {
+			OtherFeeds::get().into_iter().find(|(f, _)| f == feed).map(|(_, root)| root)
+		}
+	}
+
+	fn message_root_weight(_: &Feed) -> Weight {
+		MESSAGE_ROOT_WEIGHT
+	}
 }
 impl Config for Test {
 	type ChainId = ChainId;
 	type Event = Event;
 	type MaxAuthorities = MaxAuthorities;
+	type MaxAuthoritiesChangePendingPeriod = MaxAuthoritiesChangePendingPeriod;
 	type MaxPendingPeriod = MaxPendingPeriod;
-	type MessageRoot = MessageRoot;
+	type MaxSignatureRoundRecords = MaxSignatureRoundRecords;
+	type MessageRoots = MockMessageRoots;
+	type OnSignatureRound = SignatureRoundRecorder;
 	type SignThreshold = SignThreshold;
 	type SyncInterval = SyncInterval;
+	type UnsignedPriority = UnsignedPriority;
 	type WeightInfo = ();
 }
 
@@ -99,7 +151,7 @@ frame_support::construct_runtime! {
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
-		EcdsaAuthority: darwinia_ecdsa_authority::{Pallet, Call, Storage, Config, Event<T>}
+		EcdsaAuthority: darwinia_ecdsa_authority::{Pallet, Call, Storage, Config, Event<T>, ValidateUnsigned}
 	}
 }
 
@@ -166,6 +218,10 @@ pub(crate) fn new_message_root(byte: u8) {
 	MESSAGE_ROOT.with(|v| *v.borrow_mut() = Some(message_root_of(byte)));
 }
 
+pub(crate) fn add_feed(feed: Feed, byte: u8) {
+	OTHER_FEEDS.with(|v| v.borrow_mut().push((feed, message_root_of(byte))));
+}
+
 pub(crate) fn run_to_block(n: BlockNumber) {
 	for b in System::block_number() + 1..=n {
 		System::set_block_number(b);
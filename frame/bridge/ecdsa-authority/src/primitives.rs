@@ -1,16 +1,26 @@
-pub(crate) use sp_core::ecdsa::Signature;
+pub use sp_core::ecdsa::Signature;
 
 // --- crates.io ---
 use codec::{Decode, Encode};
+use ethabi::Token;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 // --- paritytech ---
-use sp_core::{H160, H256};
+use frame_support::{traits::Get, weights::Weight};
+use sp_core::{crypto::KeyTypeId, H160, H256};
 use sp_io::{crypto, hashing};
 use sp_runtime::RuntimeDebug;
+use sp_std::{marker::PhantomData, prelude::*, vec};
 
-pub(crate) type Address = H160;
+pub type Address = H160;
 pub(crate) type Hash = H256;
-pub(crate) type Message = [u8; 32];
+pub type Message = [u8; 32];
+/// The source of a message root. E.g. the lane ID or the commitment contract name.
+pub type RootSource = [u8; 4];
+
+/// Key type of the authority's ECDSA key, which is using by the off-chain worker.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"ecda");
 
 // address(0x1)
 pub(crate) const AUTHORITY_SENTINEL: H160 =
@@ -21,6 +31,9 @@ pub(crate) const RELAY_TYPE_HASH: H256 = H256([
 	48, 168, 41, 130, 168, 213, 5, 13, 28, 131, 187, 234, 87, 74, 234, 48, 26, 77, 49, 120, 64,
 	168, 196, 115, 74, 48, 143, 250, 166, 166, 59, 200,
 ]);
+// bytes4(keccak256("batch_change_relayers(bytes4[],bytes[],uint256)"))
+// 0x64a20cf0
+pub(crate) const BATCH_OPERATION_ID: [u8; 4] = [100, 162, 12, 240];
 // keccak256("Commitment(uint32 block_number,bytes32 message_root,uint256 nonce)");
 // 0xaca824a0c4edb3b2c17f33fea9cb21b33c7ee16c8e634c36b3bf851c9de7a223
 pub(crate) const COMMIT_TYPE_HASH: H256 = H256([
@@ -57,13 +70,46 @@ impl Sign {
 			false
 		}
 	}
+
+	/// Sign the message with all the local ECDSA keys.
+	///
+	/// Return the signer addresses and their Ethereum style signatures.
+	pub(crate) fn sign_with_local_keys(message: &Message) -> Vec<(Address, Signature)> {
+		crypto::ecdsa_public_keys(KEY_TYPE)
+			.into_iter()
+			.filter_map(|public| crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, message))
+			.filter_map(|mut signature| {
+				let public_key =
+					crypto::secp256k1_ecdsa_recover(signature.as_ref(), message).ok()?;
+
+				// Convert the recovery id to `27/28`, which is required by the `ecrecover`.
+				signature.0[64] += 27;
+
+				Some((Address::from_slice(&Self::hash(&public_key)[12..]), signature))
+			})
+			.collect()
+	}
 }
 
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Clone, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum Operation {
-	AddMember { new: Address },
-	RemoveMember { pre: Address, old: Address },
-	SwapMembers { pre: Address, old: Address, new: Address },
+	AddMember {
+		new: Address,
+	},
+	RemoveMember {
+		pre: Address,
+		old: Address,
+	},
+	SwapMembers {
+		pre: Address,
+		old: Address,
+		new: Address,
+	},
+	/// Multiple operations which share one signature round and one resulting threshold.
+	BatchMembers {
+		operations: Vec<Operation>,
+	},
 }
 impl Operation {
 	pub(crate) fn id(&self) -> [u8; 4] {
@@ -77,15 +123,181 @@ impl Operation {
 			// bytes4(keccak256("swap_relayer(address,address,address)"))
 			// 0xcb76085b
 			Self::SwapMembers { .. } => [203, 118, 8, 91],
+			Self::BatchMembers { .. } => BATCH_OPERATION_ID,
+		}
+	}
+
+	/// The operation parameters without the new threshold.
+	///
+	/// A batch is encoded as `(bytes4[] sigs, bytes[] params)`, each pair describes one operation.
+	pub(crate) fn params(&self) -> Vec<Token> {
+		match self {
+			Self::AddMember { new } => vec![Token::Address(*new)],
+			Self::RemoveMember { pre, old } => vec![Token::Address(*pre), Token::Address(*old)],
+			Self::SwapMembers { pre, old, new } =>
+				vec![Token::Address(*pre), Token::Address(*old), Token::Address(*new)],
+			Self::BatchMembers { operations } => vec![
+				Token::Array(operations.iter().map(|o| Token::FixedBytes(o.id().into())).collect()),
+				Token::Array(
+					operations.iter().map(|o| Token::Bytes(ethabi::encode(&o.params()))).collect(),
+				),
+			],
+		}
+	}
+}
+
+/// A single authority change of `batch_change_authorities`.
+#[derive(Clone, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum AuthorityChange {
+	Add { new: Address },
+	Remove { old: Address },
+	Swap { old: Address, new: Address },
+}
+
+/// An independent message root feed, identified by `(target chain id, root source)`.
+///
+/// Each feed has its own previous message root and pending commitment.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct Feed {
+	pub target_chain_id: u64,
+	pub source: RootSource,
+}
+
+/// Provide the message roots of the feeds.
+pub trait MessageRootFeeds {
+	/// All the feeds which are signed by the authorities.
+	fn feeds() -> Vec<Feed>;
+
+	/// The latest message root of the feed.
+	fn message_root(feed: &Feed) -> Option<Hash>;
+
+	/// The weight of querying the message root of the feed.
+	fn message_root_weight(feed: &Feed) -> Weight;
+}
+/// Adapt a single message root getter to a feed.
+pub struct SingleFeed<TargetChainId, Source, MessageRoot, MessageRootWeight>(
+	PhantomData<(TargetChainId, Source, MessageRoot, MessageRootWeight)>,
+);
+impl<TargetChainId, Source, MessageRoot, MessageRootWeight> MessageRootFeeds
+	for SingleFeed<TargetChainId, Source, MessageRoot, MessageRootWeight>
+where
+	TargetChainId: Get<u64>,
+	Source: Get<RootSource>,
+	MessageRoot: Get<Option<Hash>>,
+	MessageRootWeight: Get<Weight>,
+{
+	fn feeds() -> Vec<Feed> {
+		vec![Feed { target_chain_id: TargetChainId::get(), source: Source::get() }]
+	}
+
+	fn message_root(feed: &Feed) -> Option<Hash> {
+		if feed.target_chain_id == TargetChainId::get() && feed.source == Source::get() {
+			MessageRoot::get()
+		} else {
+			None
 		}
 	}
+
+	fn message_root_weight(_: &Feed) -> Weight {
+		MessageRootWeight::get()
+	}
 }
 
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Clone, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct Commitment {
-	pub(crate) block_number: u32,
-	pub(crate) message_root: Hash,
-	pub(crate) nonce: u32,
+	pub block_number: u32,
+	pub message_root: Hash,
+	pub nonce: u32,
+}
+
+/// The payload which is waiting for signing.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum SigningPayload {
+	AuthoritiesChange { operation: Operation, new_threshold: Option<u32> },
+	NewMessageRoot { feed: Feed, commitment: Commitment },
+}
+
+/// The signing progress of the pending payload.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct SigningProgress {
+	pub payload: SigningPayload,
+	/// The message which the authorities should sign.
+	pub message: Message,
+	/// The collected signatures.
+	pub signatures: Vec<(Address, Signature)>,
+	/// The number of signatures required to reach the `SignThreshold`.
+	pub threshold: u32,
+	pub authorities_count: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum RoundKind {
+	AuthoritiesChange,
+	NewMessageRoot { feed: Feed },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum RoundOutcome {
+	/// Collected enough signatures.
+	Completed,
+	/// Dropped before collecting enough signatures.
+	Expired,
+}
+
+/// The participation record of a finished signature round.
+#[derive(Clone, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct SignatureRound<BlockNumber> {
+	pub kind: RoundKind,
+	pub message: Message,
+	pub finished_at: BlockNumber,
+	pub outcome: RoundOutcome,
+	/// The authorities who submitted a valid signature.
+	pub signers: Vec<Address>,
+	/// The authorities who didn't submit any signature.
+	pub absentees: Vec<Address>,
+}
+
+/// Hook for the finished signature rounds.
+///
+/// Could be used for rewarding the signers or reporting the absentees.
+pub trait OnSignatureRound<BlockNumber> {
+	/// Handle the finished round, return the consumed weight.
+	fn on_signature_round(round: &SignatureRound<BlockNumber>) -> Weight;
+
+	/// The worst case weight of handling a round, used to bound the signature submissions.
+	fn max_weight() -> Weight;
+}
+impl<BlockNumber> OnSignatureRound<BlockNumber> for () {
+	fn on_signature_round(_: &SignatureRound<BlockNumber>) -> Weight {
+		0
+	}
+
+	fn max_weight() -> Weight {
+		0
+	}
+}
+
+/// Verify the data which is attested by the authorities.
+///
+/// Could be used by the other pallets to accept a threshold-signed proof, e.g. a bridge redemption.
+pub trait AuthoritiesVerifier {
+	/// Wrap the `data` into the message which the authorities should sign.
+	fn signable_message(data: &[u8]) -> Message;
+
+	/// Return `true` if the distinct valid signatures of the authorities reach the threshold.
+	fn verify_signatures(message: &Message, signatures: &[(Address, Signature)]) -> bool;
+
+	/// The maximum number of the authorities, used to bound the benchmarks of the other pallets.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_authorities() -> u32;
+
+	/// Replace the authorities, used to set up the benchmarks of the other pallets.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_authorities(authorities: Vec<Address>);
 }
 
 #[test]
@@ -1,5 +1,16 @@
+// --- std ---
+use std::sync::Arc;
 // --- paritytech ---
-use frame_support::{assert_noop, assert_ok, weights::PostDispatchInfo};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OffchainWorker, OnInitialize},
+	weights::PostDispatchInfo,
+};
+use sp_core::offchain::{
+	testing::{TestOffchainExt, TestTransactionPoolExt},
+	OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
+use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
 // --- darwinia-network ---
 use crate::{mock::*, Event, *};
 
@@ -197,13 +208,159 @@ fn swap_authority() {
 	});
 }
 
+#[test]
+fn batch_change_authorities() {
+	let address_1 = Address::repeat_byte(1);
+	let address_2 = Address::repeat_byte(2);
+	let address_3 = Address::repeat_byte(3);
+	let address_4 = Address::repeat_byte(4);
+
+	ExtBuilder::default().authorities(vec![address_1, address_2]).build().execute_with(|| {
+		// Case 1.
+		assert_noop!(
+			EcdsaAuthority::batch_change_authorities(
+				Origin::signed(Default::default()),
+				vec![AuthorityChange::Add { new: address_3 }]
+			),
+			DispatchError::BadOrigin
+		);
+
+		// Case 2.
+		assert_noop!(
+			EcdsaAuthority::batch_change_authorities(Origin::root(), Vec::new()),
+			EcdsaAuthorityError::EmptyBatch
+		);
+
+		// Case 3.
+		assert_noop!(
+			EcdsaAuthority::batch_change_authorities(
+				Origin::root(),
+				vec![AuthorityChange::Add { new: address_3 }; MaxAuthorities::get() as usize + 1]
+			),
+			EcdsaAuthorityError::TooManyChanges
+		);
+
+		// Case 4.
+		assert_noop!(
+			EcdsaAuthority::batch_change_authorities(
+				Origin::root(),
+				vec![
+					AuthorityChange::Add { new: address_3 },
+					AuthorityChange::Remove { old: address_4 }
+				]
+			),
+			EcdsaAuthorityError::NotAuthority
+		);
+
+		assert_ok!(EcdsaAuthority::batch_change_authorities(
+			Origin::root(),
+			vec![
+				AuthorityChange::Swap { old: address_1, new: address_3 },
+				AuthorityChange::Add { new: address_4 },
+				AuthorityChange::Remove { old: address_2 },
+			]
+		));
+		assert_eq!(EcdsaAuthority::authorities(), vec![address_1, address_2]);
+		assert_eq!(EcdsaAuthority::next_authorities(), vec![address_4, address_3]);
+		assert_eq!(EcdsaAuthority::nonce(), 0);
+		let operation = Operation::BatchMembers {
+			operations: vec![
+				Operation::SwapMembers { pre: AUTHORITY_SENTINEL, old: address_1, new: address_3 },
+				Operation::AddMember { new: address_4 },
+				Operation::RemoveMember { pre: address_3, old: address_2 },
+			],
+		};
+		let message = [
+			165, 35, 82, 9, 106, 231, 59, 61, 251, 9, 122, 120, 156, 94, 111, 82, 92, 43, 254, 130,
+			15, 250, 155, 162, 80, 153, 236, 8, 169, 127, 65, 196,
+		];
+		assert_eq!(
+			EcdsaAuthority::authorities_change_to_sign(),
+			Some((operation, Some(2), message, Default::default()))
+		);
+		assert_eq!(
+			ecdsa_authority_events(),
+			vec![Event::CollectingAuthoritiesChangeSignatures { message }]
+		);
+
+		// Case 5.
+		assert_noop!(
+			EcdsaAuthority::batch_change_authorities(
+				Origin::root(),
+				vec![AuthorityChange::Remove { old: address_3 }]
+			),
+			EcdsaAuthorityError::OnAuthoritiesChange
+		);
+		presume_authority_change_succeed();
+		assert_eq!(EcdsaAuthority::authorities(), vec![address_4, address_3]);
+		assert_eq!(EcdsaAuthority::nonce(), 1);
+	});
+}
+
+#[test]
+fn authorities_change_timeout() {
+	let (secret_key_1, address_1) = gen_pair(1);
+	let (secret_key_2, address_2) = gen_pair(2);
+	let (_, address_3) = gen_pair(3);
+
+	ExtBuilder::default().authorities(vec![address_1, address_2]).build().execute_with(|| {
+		// Case 1.
+		assert_ok!(EcdsaAuthority::add_authority(Origin::root(), address_3));
+		let (operation, new_threshold, message, _) =
+			EcdsaAuthority::authorities_change_to_sign().unwrap();
+		assert_eq!(EcdsaAuthority::authorities_change_started_at(), Some(1));
+		ecdsa_authority_events();
+		run_to_block(1 + MaxAuthoritiesChangePendingPeriod::get());
+		assert!(EcdsaAuthority::authorities_change_to_sign().is_some());
+		assert!(ecdsa_authority_events().is_empty());
+		run_to_block(2 + MaxAuthoritiesChangePendingPeriod::get());
+		assert!(EcdsaAuthority::authorities_change_to_sign().is_none());
+		assert!(EcdsaAuthority::authorities_change_started_at().is_none());
+		assert_eq!(EcdsaAuthority::authorities(), vec![address_1, address_2]);
+		assert_eq!(EcdsaAuthority::next_authorities(), vec![address_1, address_2]);
+		assert_eq!(EcdsaAuthority::nonce(), 0);
+		assert_eq!(
+			ecdsa_authority_events(),
+			vec![Event::AuthoritiesChangeExpired { operation: operation.clone(), message }]
+		);
+		run_to_block(3 + 2 * MaxAuthoritiesChangePendingPeriod::get());
+		assert_eq!(
+			(0..EcdsaAuthority::signature_round_count())
+				.filter_map(EcdsaAuthority::signature_round_of)
+				.filter(|round| round.kind == RoundKind::AuthoritiesChange)
+				.count(),
+			1
+		);
+
+		// Case 2.
+		assert_ok!(EcdsaAuthority::add_authority(Origin::root(), address_3));
+		assert_eq!(
+			EcdsaAuthority::authorities_change_to_sign(),
+			Some((operation, new_threshold, message, Default::default()))
+		);
+		assert_eq!(EcdsaAuthority::authorities_change_started_at(), Some(System::block_number()));
+		assert_ok!(EcdsaAuthority::submit_authorities_change_signature(
+			Origin::signed(Default::default()),
+			address_1,
+			sign(&secret_key_1, &message),
+		));
+		assert_ok!(EcdsaAuthority::submit_authorities_change_signature(
+			Origin::signed(Default::default()),
+			address_2,
+			sign(&secret_key_2, &message),
+		));
+		assert_eq!(EcdsaAuthority::authorities(), vec![address_3, address_1, address_2]);
+		assert_eq!(EcdsaAuthority::nonce(), 1);
+	});
+}
+
 #[test]
 fn sync_interval_and_max_pending_period() {
 	ExtBuilder::default().build().execute_with(|| {
 		// Check new message root while reaching the sync interval checkpoint.
 		(2..SyncInterval::get()).for_each(|i| {
 			run_to_block(i);
-			assert!(EcdsaAuthority::new_message_root_to_sign().is_none());
+			assert!(EcdsaAuthority::new_message_root_to_sign(FEED).is_none());
 		});
 		run_to_block(SyncInterval::get());
 		let message = [
@@ -211,7 +368,7 @@ fn sync_interval_and_max_pending_period() {
 			211, 254, 16, 3, 191, 15, 171, 104, 151, 60, 37, 145, 208, 225,
 		];
 		assert_eq!(
-			EcdsaAuthority::new_message_root_to_sign(),
+			EcdsaAuthority::new_message_root_to_sign(FEED),
 			Some((
 				Commitment {
 					block_number: System::block_number() as _,
@@ -224,7 +381,7 @@ fn sync_interval_and_max_pending_period() {
 		);
 		assert_eq!(
 			ecdsa_authority_events(),
-			vec![Event::CollectingNewMessageRootSignatures { message }]
+			vec![Event::CollectingNewMessageRootSignatures { feed: FEED, message }]
 		);
 
 		// Use a new message root while exceeding the max pending period.
@@ -233,7 +390,7 @@ fn sync_interval_and_max_pending_period() {
 		(offset..offset + MaxPendingPeriod::get()).for_each(|i| {
 			run_to_block(i);
 			assert_eq!(
-				EcdsaAuthority::new_message_root_to_sign(),
+				EcdsaAuthority::new_message_root_to_sign(FEED),
 				Some((
 					Commitment { block_number: 3, message_root: Default::default(), nonce: 0 },
 					message,
@@ -247,7 +404,7 @@ fn sync_interval_and_max_pending_period() {
 			237, 231, 40, 180, 127, 138, 138, 21, 158, 23, 116, 176, 7,
 		];
 		assert_eq!(
-			EcdsaAuthority::new_message_root_to_sign(),
+			EcdsaAuthority::new_message_root_to_sign(FEED),
 			Some((
 				Commitment { block_number: 9, message_root: message_root_of(1), nonce: 0 },
 				message,
@@ -262,7 +419,7 @@ fn sync_interval_and_max_pending_period() {
 		(offset..=offset + MaxPendingPeriod::get()).for_each(|i| {
 			run_to_block(i);
 			assert_eq!(
-				EcdsaAuthority::new_message_root_to_sign(),
+				EcdsaAuthority::new_message_root_to_sign(FEED),
 				Some((
 					Commitment { block_number: 9, message_root: message_root_of(1), nonce: 0 },
 					message,
@@ -273,6 +430,61 @@ fn sync_interval_and_max_pending_period() {
 	});
 }
 
+#[test]
+fn multiple_feeds() {
+	let (secret_key_1, address_1) = gen_pair(1);
+	let (secret_key_2, address_2) = gen_pair(2);
+	let feed = Feed { target_chain_id: 2, source: [0; 4] };
+
+	add_feed(feed, 1);
+	ExtBuilder::default().authorities(vec![address_1, address_2]).build().execute_with(|| {
+		run_to_block(SyncInterval::get());
+		let (commitment, message, _) = EcdsaAuthority::new_message_root_to_sign(feed).unwrap();
+		let (_, message_, _) = EcdsaAuthority::new_message_root_to_sign(FEED).unwrap();
+		assert_eq!(
+			commitment,
+			Commitment { block_number: 3, message_root: message_root_of(1), nonce: 0 }
+		);
+		assert_ne!(message, message_);
+		assert_eq!(EcdsaAuthority::previous_message_root(feed), Some((3, message_root_of(1))));
+		assert_eq!(EcdsaAuthority::previous_message_root(FEED), Some((3, Default::default())));
+		assert_eq!(
+			ecdsa_authority_events(),
+			vec![
+				Event::CollectingNewMessageRootSignatures { feed: FEED, message: message_ },
+				Event::CollectingNewMessageRootSignatures { feed, message }
+			]
+		);
+
+		// The signature of the other feed is invalid.
+		assert_noop!(
+			EcdsaAuthority::submit_new_message_root_signature(
+				Origin::signed(Default::default()),
+				FEED,
+				address_1,
+				sign(&secret_key_1, &message),
+			),
+			EcdsaAuthorityError::BadSignature
+		);
+
+		// Each feed collects the signatures independently.
+		assert_ok!(EcdsaAuthority::submit_new_message_root_signature(
+			Origin::signed(Default::default()),
+			feed,
+			address_1,
+			sign(&secret_key_1, &message),
+		));
+		assert_ok!(EcdsaAuthority::submit_new_message_root_signature(
+			Origin::signed(Default::default()),
+			feed,
+			address_2,
+			sign(&secret_key_2, &message),
+		));
+		assert!(EcdsaAuthority::new_message_root_to_sign(feed).is_none());
+		assert!(EcdsaAuthority::new_message_root_to_sign(FEED).is_some());
+	});
+}
+
 #[test]
 fn submit_authorities_change_signature() {
 	let (secret_key_1, address_1) = gen_pair(1);
@@ -351,6 +563,7 @@ fn submit_authorities_change_signature() {
 					signatures: vec![(address_1, signature_1), (address_2, signature_2)]
 				},
 				Event::CollectingNewMessageRootSignatures {
+					feed: FEED,
 					message: [
 						154, 219, 45, 185, 181, 249, 194, 236, 54, 17, 201, 121, 48, 58, 30, 38,
 						23, 204, 118, 118, 94, 117, 242, 172, 64, 251, 245, 74, 235, 49, 46, 132
@@ -372,6 +585,7 @@ fn submit_new_message_root_signature() {
 		assert_noop!(
 			EcdsaAuthority::submit_new_message_root_signature(
 				Origin::signed(Default::default()),
+				FEED,
 				address_1,
 				Default::default(),
 			),
@@ -384,7 +598,7 @@ fn submit_new_message_root_signature() {
 			211, 254, 16, 3, 191, 15, 171, 104, 151, 60, 37, 145, 208, 225,
 		];
 		assert_eq!(
-			EcdsaAuthority::new_message_root_to_sign(),
+			EcdsaAuthority::new_message_root_to_sign(FEED),
 			Some((
 				Commitment {
 					block_number: System::block_number() as _,
@@ -397,13 +611,14 @@ fn submit_new_message_root_signature() {
 		);
 		assert_eq!(
 			ecdsa_authority_events(),
-			vec![Event::CollectingNewMessageRootSignatures { message }]
+			vec![Event::CollectingNewMessageRootSignatures { feed: FEED, message }]
 		);
 
 		// Case 2.
 		assert_noop!(
 			EcdsaAuthority::submit_new_message_root_signature(
 				Origin::signed(Default::default()),
+				FEED,
 				address_1,
 				Default::default(),
 			),
@@ -415,6 +630,7 @@ fn submit_new_message_root_signature() {
 		assert_noop!(
 			EcdsaAuthority::submit_new_message_root_signature(
 				Origin::signed(Default::default()),
+				FEED,
 				address_3,
 				signature_3,
 			),
@@ -426,11 +642,12 @@ fn submit_new_message_root_signature() {
 		assert_eq!(EcdsaAuthority::nonce(), nonce);
 		assert_ok!(EcdsaAuthority::submit_new_message_root_signature(
 			Origin::signed(Default::default()),
+			FEED,
 			address_1,
 			signature_1.clone(),
 		));
 		assert_eq!(
-			EcdsaAuthority::new_message_root_to_sign(),
+			EcdsaAuthority::new_message_root_to_sign(FEED),
 			Some((
 				Commitment {
 					block_number: System::block_number() as _,
@@ -445,14 +662,16 @@ fn submit_new_message_root_signature() {
 		let signature_2 = sign(&secret_key_2, &message);
 		assert_ok!(EcdsaAuthority::submit_new_message_root_signature(
 			Origin::signed(Default::default()),
+			FEED,
 			address_2,
 			signature_2.clone(),
 		));
 		assert_eq!(EcdsaAuthority::nonce(), nonce);
-		assert!(EcdsaAuthority::new_message_root_to_sign().is_none());
+		assert!(EcdsaAuthority::new_message_root_to_sign(FEED).is_none());
 		assert_eq!(
 			ecdsa_authority_events(),
 			vec![Event::CollectedEnoughNewMessageRootSignatures {
+				feed: FEED,
 				commitment: Commitment {
 					block_number: System::block_number() as _,
 					message_root: Default::default(),
@@ -465,6 +684,245 @@ fn submit_new_message_root_signature() {
 	});
 }
 
+#[test]
+fn signature_round_handler_should_be_weighed() {
+	let (secret_key_1, address_1) = gen_pair(1);
+	let (secret_key_2, address_2) = gen_pair(2);
+	let (_, address_3) = gen_pair(3);
+
+	ExtBuilder::default().authorities(vec![address_1, address_2, address_3]).build().execute_with(
+		|| {
+			run_to_block(SyncInterval::get());
+			let (_, message, _) = EcdsaAuthority::new_message_root_to_sign(FEED).unwrap();
+			assert_eq!(
+				EcdsaAuthority::submit_new_message_root_signature(
+					Origin::signed(Default::default()),
+					FEED,
+					address_1,
+					sign(&secret_key_1, &message),
+				),
+				Ok(PostDispatchInfo { actual_weight: Some(SUBMISSION_WEIGHT), pays_fee: Pays::No })
+			);
+			// The submission which finishes the round is charged for the handler.
+			assert_eq!(
+				EcdsaAuthority::submit_new_message_root_signature(
+					Origin::signed(Default::default()),
+					FEED,
+					address_2,
+					sign(&secret_key_2, &message),
+				),
+				Ok(PostDispatchInfo {
+					actual_weight: Some(SUBMISSION_WEIGHT + SIGNATURE_ROUND_WEIGHT),
+					pays_fee: Pays::No
+				})
+			);
+
+			// So does the block which expires the round, along with the message root query.
+			assert_ok!(EcdsaAuthority::remove_authority(Origin::root(), address_3));
+			let expired_at = System::block_number() + MaxAuthoritiesChangePendingPeriod::get() + 1;
+			assert_eq!(expired_at % SyncInterval::get(), 0);
+			run_to_block(expired_at - 1);
+			System::set_block_number(expired_at);
+			assert_eq!(
+				<EcdsaAuthority as OnInitialize<_>>::on_initialize(expired_at),
+				SIGNATURE_ROUND_WEIGHT + MESSAGE_ROOT_WEIGHT
+			);
+			assert!(EcdsaAuthority::authorities_change_to_sign().is_none());
+		},
+	);
+}
+
+#[test]
+fn signature_round_records() {
+	let (secret_key_1, address_1) = gen_pair(1);
+	let (secret_key_2, address_2) = gen_pair(2);
+	let (_, address_3) = gen_pair(3);
+
+	ExtBuilder::default().authorities(vec![address_1, address_2, address_3]).build().execute_with(
+		|| {
+			// Case 1.
+			run_to_block(SyncInterval::get());
+			let (_, message, _) = EcdsaAuthority::new_message_root_to_sign(FEED).unwrap();
+			assert_ok!(EcdsaAuthority::submit_new_message_root_signature(
+				Origin::signed(Default::default()),
+				FEED,
+				address_1,
+				sign(&secret_key_1, &message),
+			));
+			assert_eq!(EcdsaAuthority::signature_round_count(), 0);
+			assert_ok!(EcdsaAuthority::submit_new_message_root_signature(
+				Origin::signed(Default::default()),
+				FEED,
+				address_2,
+				sign(&secret_key_2, &message),
+			));
+			let round_0 = SignatureRound {
+				kind: RoundKind::NewMessageRoot { feed: FEED },
+				message,
+				finished_at: 3,
+				outcome: RoundOutcome::Completed,
+				signers: vec![address_1, address_2],
+				absentees: vec![address_3],
+			};
+			assert_eq!(EcdsaAuthority::signature_round_count(), 1);
+			assert_eq!(EcdsaAuthority::signature_round_of(0), Some(round_0.clone()));
+
+			// Case 2.
+			assert_ok!(EcdsaAuthority::remove_authority(Origin::root(), address_3));
+			let (_, _, message, _) = EcdsaAuthority::authorities_change_to_sign().unwrap();
+			run_to_block(SyncInterval::get() + MaxAuthoritiesChangePendingPeriod::get() + 1);
+			let round_1 = SignatureRound {
+				kind: RoundKind::AuthoritiesChange,
+				message,
+				finished_at: 12,
+				outcome: RoundOutcome::Expired,
+				signers: Vec::new(),
+				absentees: vec![address_1, address_2, address_3],
+			};
+			assert_eq!(EcdsaAuthority::signature_round_count(), 2);
+			assert_eq!(EcdsaAuthority::signature_round_of(1), Some(round_1.clone()));
+
+			// Case 3.
+			assert_ok!(EcdsaAuthority::remove_authority(Origin::root(), address_3));
+			let (_, _, message, _) = EcdsaAuthority::authorities_change_to_sign().unwrap();
+			assert_ok!(EcdsaAuthority::submit_authorities_change_signature(
+				Origin::signed(Default::default()),
+				address_1,
+				sign(&secret_key_1, &message),
+			));
+			assert_ok!(EcdsaAuthority::submit_authorities_change_signature(
+				Origin::signed(Default::default()),
+				address_2,
+				sign(&secret_key_2, &message),
+			));
+			let round_2 = SignatureRound {
+				kind: RoundKind::AuthoritiesChange,
+				message,
+				finished_at: 12,
+				outcome: RoundOutcome::Completed,
+				signers: vec![address_1, address_2],
+				absentees: vec![address_3],
+			};
+			assert_eq!(EcdsaAuthority::signature_round_count(), 3);
+			assert_eq!(EcdsaAuthority::signature_round_of(2), Some(round_2.clone()));
+
+			// Case 4.
+			let (_, message, _) = EcdsaAuthority::new_message_root_to_sign(FEED).unwrap();
+			new_message_root(1);
+			run_to_block(System::block_number() + MaxPendingPeriod::get() + 1);
+			let round_3 = SignatureRound {
+				kind: RoundKind::NewMessageRoot { feed: FEED },
+				message,
+				finished_at: 18,
+				outcome: RoundOutcome::Expired,
+				signers: Vec::new(),
+				absentees: vec![address_1, address_2],
+			};
+			assert_eq!(EcdsaAuthority::signature_round_count(), 4);
+			assert_eq!(EcdsaAuthority::signature_round_of(3), Some(round_3.clone()));
+
+			// Only keep the latest `MaxSignatureRoundRecords` records.
+			assert!(EcdsaAuthority::signature_round_of(0).is_none());
+			assert_eq!(FinishedSignatureRounds::get(), vec![round_0, round_1, round_2, round_3]);
+		},
+	);
+}
+
+#[test]
+fn signing_progress() {
+	let (secret_key_1, address_1) = gen_pair(1);
+	let (_, address_2) = gen_pair(2);
+	let (_, address_3) = gen_pair(3);
+
+	ExtBuilder::default().authorities(vec![address_1, address_2]).build().execute_with(|| {
+		assert!(EcdsaAuthority::pending_authorities_change().is_none());
+		assert!(EcdsaAuthority::pending_new_message_root(FEED).is_none());
+
+		run_to_block(SyncInterval::get());
+		let (commitment, message, _) = EcdsaAuthority::new_message_root_to_sign(FEED).unwrap();
+		let signature_1 = sign(&secret_key_1, &message);
+		assert_ok!(EcdsaAuthority::submit_new_message_root_signature(
+			Origin::signed(Default::default()),
+			FEED,
+			address_1,
+			signature_1.clone(),
+		));
+		assert_eq!(
+			EcdsaAuthority::pending_new_message_root(FEED),
+			Some(SigningProgress {
+				payload: SigningPayload::NewMessageRoot { feed: FEED, commitment },
+				message,
+				signatures: vec![(address_1, signature_1)],
+				threshold: 2,
+				authorities_count: 2,
+			})
+		);
+
+		assert_ok!(EcdsaAuthority::add_authority(Origin::root(), address_3));
+		let (operation, new_threshold, message, _) =
+			EcdsaAuthority::authorities_change_to_sign().unwrap();
+		assert_eq!(
+			EcdsaAuthority::pending_authorities_change(),
+			Some(SigningProgress {
+				payload: SigningPayload::AuthoritiesChange { operation, new_threshold },
+				message,
+				signatures: Vec::new(),
+				threshold: 2,
+				authorities_count: 2,
+			})
+		);
+	});
+}
+
+#[test]
+fn verify_commitment() {
+	let (secret_key_1, address_1) = gen_pair(1);
+	let (secret_key_2, address_2) = gen_pair(2);
+	let (_, address_3) = gen_pair(3);
+	let (secret_key_4, address_4) = gen_pair(4);
+
+	ExtBuilder::default().authorities(vec![address_1, address_2, address_3]).build().execute_with(
+		|| {
+			run_to_block(SyncInterval::get());
+			let (commitment, message, _) = EcdsaAuthority::new_message_root_to_sign(FEED).unwrap();
+			let signature_1 = (address_1, sign(&secret_key_1, &message));
+			let signature_2 = (address_2, sign(&secret_key_2, &message));
+
+			assert!(EcdsaAuthority::verify_commitment(
+				&commitment,
+				&[signature_1.clone(), signature_2.clone()]
+			));
+
+			// Not enough signatures.
+			assert!(!EcdsaAuthority::verify_commitment(&commitment, &[signature_1.clone()]));
+			// Duplicated signatures.
+			assert!(!EcdsaAuthority::verify_commitment(
+				&commitment,
+				&[signature_1.clone(), signature_1.clone()]
+			));
+			// Not an authority.
+			assert!(!EcdsaAuthority::verify_commitment(
+				&commitment,
+				&[signature_1.clone(), (address_4, sign(&secret_key_4, &message))]
+			));
+			// Bad signature.
+			assert!(!EcdsaAuthority::verify_commitment(
+				&commitment,
+				&[signature_1.clone(), (address_2, sign(&secret_key_2, &[0; 32]))]
+			));
+			// Different commitment.
+			assert!(!EcdsaAuthority::verify_commitment(
+				&Commitment { nonce: 1, ..commitment.clone() },
+				&[signature_1.clone(), signature_2.clone()]
+			));
+			assert!(!EcdsaAuthority::verify_commitment(
+				&Commitment { message_root: message_root_of(1), ..commitment },
+				&[signature_1, signature_2]
+			));
+		},
+	);
+}
+
 #[test]
 fn tx_fee() {
 	let (secret_key_1, address_1) = gen_pair(1);
@@ -482,16 +940,18 @@ fn tx_fee() {
 		assert_eq!(
 			EcdsaAuthority::submit_new_message_root_signature(
 				Origin::signed(Default::default()),
+				FEED,
 				address_1,
 				sign(&secret_key_1, &message),
 			),
-			Ok(PostDispatchInfo { actual_weight: None, pays_fee: Pays::No })
+			Ok(PostDispatchInfo { actual_weight: Some(SUBMISSION_WEIGHT), pays_fee: Pays::No })
 		);
 
 		// Forbidden for submitting multiple times once the previous one succeeds.
 		assert_noop!(
 			EcdsaAuthority::submit_new_message_root_signature(
 				Origin::signed(Default::default()),
+				FEED,
 				address_1,
 				Default::default(),
 			),
@@ -511,7 +971,7 @@ fn tx_fee() {
 				address_1,
 				sign(&secret_key_1, &message),
 			),
-			Ok(PostDispatchInfo { actual_weight: None, pays_fee: Pays::No })
+			Ok(PostDispatchInfo { actual_weight: Some(SUBMISSION_WEIGHT), pays_fee: Pays::No })
 		);
 
 		// Forbidden for submitting multiple times once the previous one succeeds.
@@ -525,3 +985,122 @@ fn tx_fee() {
 		);
 	});
 }
+
+#[test]
+fn offchain_worker() {
+	let (_, address_1) = gen_pair(1);
+	let (_, address_2) = gen_pair(2);
+	let mut ext = ExtBuilder::default().authorities(vec![address_1, address_2]).build();
+	let (offchain, _) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let keystore = KeyStore::new();
+
+	// Same as the `gen_pair(1)`.
+	SyncCryptoStore::ecdsa_generate_new(
+		&keystore,
+		KEY_TYPE,
+		Some(&format!("0x{}", "01".repeat(32))),
+	)
+	.unwrap();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(keystore)));
+	ext.execute_with(|| {
+		// Nothing to sign.
+		<EcdsaAuthority as OffchainWorker<_>>::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+
+		run_to_block(3);
+		let (_, message, _) = EcdsaAuthority::new_message_root_to_sign(FEED).unwrap();
+
+		<EcdsaAuthority as OffchainWorker<_>>::offchain_worker(3);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		let call = match tx.call {
+			crate::mock::Call::EcdsaAuthority(call) => call,
+			_ => unreachable!(),
+		};
+		let signature = match &call {
+			crate::Call::submit_new_message_root_signature { feed, address, signature } => {
+				assert_eq!(feed, &FEED);
+				assert_eq!(address, &address_1);
+
+				signature.clone()
+			},
+			_ => unreachable!(),
+		};
+		assert!(Sign::verify_signature(&signature, &message, &address_1));
+		assert!(signature.0[64] >= 27);
+
+		assert!(EcdsaAuthority::validate_unsigned(TransactionSource::External, &call).is_ok());
+		assert_eq!(
+			EcdsaAuthority::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::submit_new_message_root_signature {
+					feed: FEED,
+					address: address_2,
+					signature: signature.clone()
+				}
+			),
+			InvalidTransaction::BadProof.into()
+		);
+		assert_ok!(EcdsaAuthority::submit_new_message_root_signature(
+			Origin::none(),
+			FEED,
+			address_1,
+			signature.clone()
+		));
+		assert_eq!(
+			EcdsaAuthority::new_message_root_to_sign(FEED).unwrap().2,
+			vec![(address_1, signature)]
+		);
+
+		// Already submitted.
+		assert_eq!(
+			EcdsaAuthority::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Stale.into()
+		);
+		<EcdsaAuthority as OffchainWorker<_>>::offchain_worker(3);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn migrate_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let new_message_root_to_sign = (
+			Commitment { block_number: 3, message_root: message_root_of(1), nonce: 0 },
+			[1; 32],
+			BoundedVec::<(Address, Signature), MaxAuthorities>::default(),
+		);
+		let previous_message_root = (3, message_root_of(1));
+
+		frame_support::migration::put_storage_value(
+			b"EcdsaAuthority",
+			b"NewMessageRootToSign",
+			&[],
+			new_message_root_to_sign.clone(),
+		);
+		frame_support::migration::put_storage_value(
+			b"EcdsaAuthority",
+			b"PreviousMessageRoot",
+			&[],
+			previous_message_root,
+		);
+		StorageVersion::new(0).put::<EcdsaAuthority>();
+		migration::migrate::<Test>();
+
+		assert_eq!(StorageVersion::get::<EcdsaAuthority>(), STORAGE_VERSION);
+		assert_eq!(EcdsaAuthority::new_message_root_to_sign(FEED), Some(new_message_root_to_sign));
+		assert_eq!(EcdsaAuthority::previous_message_root(FEED), Some(previous_message_root));
+		assert!(frame_support::migration::get_storage_value::<(u64, Hash)>(
+			b"EcdsaAuthority",
+			b"PreviousMessageRoot",
+			&[]
+		)
+		.is_none());
+	});
+}
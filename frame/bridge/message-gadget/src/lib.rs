@@ -24,54 +24,183 @@ mod mock;
 // --- core ---
 use core::marker::PhantomData;
 // --- darwinia-network ---
-use darwinia_evm::Runner;
+use darwinia_evm::{GasWeightMapping, Runner};
 // --- paritytech ---
 use frame_support::{log, pallet_prelude::*, traits::Get};
 use frame_system::pallet_prelude::*;
 use sp_core::{H160, H256};
 use sp_io::hashing;
-use sp_std::vec;
+use sp_std::{prelude::*, vec};
 
 #[frame_support::pallet]
 pub mod pallet {
 	// --- darwinia-network ---
 	use crate::*;
 
+	/// The name of a commitment contract. E.g. the lane ID or the target chain name.
+	pub type CommitmentContractName = [u8; 4];
+
 	#[pallet::config]
-	pub trait Config: frame_system::Config {}
+	pub trait Config: frame_system::Config + darwinia_evm::Config {
+		// Overrides.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The origin which could manage the commitment contracts.
+		type ManagerOrigin: EnsureOrigin<Self::Origin>;
+		/// The expected `keccak256` hash of the commitment contract's code.
+		///
+		/// If set, only the contract with the matched code is allowed to be the commitment
+		/// contract.
+		type ExpectedCodeHash: Get<Option<H256>>;
+		/// The selector of the commitment contract's message root getter.
+		///
+		/// `bytes4(keccak256("commitment()"))` by default.
+		#[pallet::constant]
+		type CommitmentSelector: Get<[u8; 4]>;
+		/// The gas limit of the message root getting call.
+		#[pallet::constant]
+		type CommitmentGasLimit: Get<u64>;
+		/// The caller of the message root getting call.
+		#[pallet::constant]
+		type CommitmentCaller: Get<H160>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The default commitment contract changed.
+		CommitmentContractSet { previous: H160, new: H160 },
+		/// The named commitment contract changed. `None` means not set or removed.
+		NamedCommitmentContractSet {
+			name: CommitmentContractName,
+			previous: Option<H160>,
+			new: Option<H160>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No code is deployed at the commitment contract address.
+		ContractCodeEmpty,
+		/// The commitment contract's code hash doesn't match the expected one.
+		CodeHashMismatch,
+	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn commitment_contract)]
 	pub type CommitmentContract<T> = StorageValue<_, H160, ValueQuery>;
 
+	/// The named commitment contracts.
+	#[pallet::storage]
+	#[pallet::getter(fn named_commitment_contract)]
+	pub type CommitmentContracts<T> =
+		StorageMap<_, Twox64Concat, CommitmentContractName, H160, OptionQuery>;
+
+	/// The message roots of the current block, keyed by the commitment contract.
+	///
+	/// Transient storage, only the first query of the block will execute the EVM call.
+	#[pallet::storage]
+	pub type CachedMessageRoots<T: Config> =
+		StorageMap<_, Twox64Concat, H160, (BlockNumberFor<T>, Option<H256>), OptionQuery>;
+
 	#[cfg_attr(feature = "std", derive(Default))]
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		pub commitment_contract: H160,
+		pub commitment_contracts: Vec<(CommitmentContractName, H160)>,
 	}
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig {
 		fn build(&self) {
-			<CommitmentContract<T>>::put(H160::default());
+			<CommitmentContract<T>>::put(self.commitment_contract);
+
+			self.commitment_contracts.iter().for_each(|(name, commitment_contract)| {
+				<CommitmentContracts<T>>::insert(name, commitment_contract);
+			});
 		}
 	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+			// Cleaning the cache in `on_finalize`.
+			//
+			// The message root queries are charged by the callers, see `MessageRootWeight`.
+			T::DbWeight::get().writes(1)
+		}
+
+		fn on_finalize(_: BlockNumberFor<T>) {
+			let _ = <CachedMessageRoots<T>>::remove_all(None);
+		}
+	}
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 1))]
 		pub fn set_commitment_contract(
 			origin: OriginFor<T>,
 			commitment_contract: H160,
 		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			Self::ensure_commitment_contract(&commitment_contract)?;
+
+			let previous = <CommitmentContract<T>>::get();
 
 			<CommitmentContract<T>>::put(commitment_contract);
 
+			Self::deposit_event(Event::<T>::CommitmentContractSet {
+				previous,
+				new: commitment_contract,
+			});
+
+			Ok(().into())
+		}
+
+		/// Set or remove(`None`) the named commitment contract.
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 1))]
+		pub fn set_named_commitment_contract(
+			origin: OriginFor<T>,
+			name: CommitmentContractName,
+			commitment_contract: Option<H160>,
+		) -> DispatchResultWithPostInfo {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			if let Some(commitment_contract) = &commitment_contract {
+				Self::ensure_commitment_contract(commitment_contract)?;
+			}
+
+			let previous = <CommitmentContracts<T>>::mutate_exists(name, |maybe_contract| {
+				core::mem::replace(maybe_contract, commitment_contract)
+			});
+
+			Self::deposit_event(Event::<T>::NamedCommitmentContractSet {
+				name,
+				previous,
+				new: commitment_contract,
+			});
+
 			Ok(().into())
 		}
 	}
+	impl<T: Config> Pallet<T> {
+		fn ensure_commitment_contract(commitment_contract: &H160) -> DispatchResult {
+			ensure!(
+				!<darwinia_evm::Pallet<T>>::is_contract_code_empty(commitment_contract),
+				<Error<T>>::ContractCodeEmpty
+			);
+
+			if let Some(expected_code_hash) = T::ExpectedCodeHash::get() {
+				let code_hash = H256(hashing::keccak_256(
+					&<darwinia_evm::Pallet<T>>::account_codes(commitment_contract),
+				));
+
+				ensure!(code_hash == expected_code_hash, <Error<T>>::CodeHashMismatch);
+			}
+
+			Ok(())
+		}
+	}
 }
 pub use pallet::*;
 
@@ -83,12 +212,41 @@ where
 	T: Config + darwinia_evm::Config,
 {
 	fn get() -> Option<H256> {
+		Self::message_root_of(<CommitmentContract<T>>::get())
+	}
+}
+impl<T> MessageRootGetter<T>
+where
+	T: Config + darwinia_evm::Config,
+{
+	/// Get the message root from the named commitment contract.
+	pub fn named(name: &CommitmentContractName) -> Option<H256> {
+		Self::message_root_of(<CommitmentContracts<T>>::get(name)?)
+	}
+
+	fn message_root_of(commitment_contract: H160) -> Option<H256> {
+		let now = <frame_system::Pallet<T>>::block_number();
+
+		if let Some((cached_at, message_root)) = <CachedMessageRoots<T>>::get(commitment_contract) {
+			if cached_at == now {
+				return message_root;
+			}
+		}
+
+		let message_root = Self::call_commitment_contract(commitment_contract);
+
+		<CachedMessageRoots<T>>::insert(commitment_contract, (now, message_root));
+
+		message_root
+	}
+
+	fn call_commitment_contract(commitment_contract: H160) -> Option<H256> {
 		if let Ok(info) = <T as darwinia_evm::Config>::Runner::call(
-			H160::default(),
-			<CommitmentContract<T>>::get(),
-			hashing::keccak_256(b"commitment()")[..4].to_vec(),
+			T::CommitmentCaller::get(),
+			commitment_contract,
+			T::CommitmentSelector::get().to_vec(),
 			0.into(),
-			1_000_000_000_000,
+			T::CommitmentGasLimit::get(),
 			None,
 			None,
 			None,
@@ -112,3 +270,29 @@ where
 		None
 	}
 }
+
+/// The worst case weight of a message root query.
+///
+/// Executing the commitment call with `CommitmentGasLimit`, reading and writing the cache.
+pub struct MessageRootWeight<T>(PhantomData<T>);
+impl<T> Get<Weight> for MessageRootWeight<T>
+where
+	T: Config + darwinia_evm::Config,
+{
+	fn get() -> Weight {
+		<T as darwinia_evm::Config>::GasWeightMapping::gas_to_weight(T::CommitmentGasLimit::get())
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+}
+
+/// Get the message root from the named commitment contract.
+pub struct NamedMessageRootGetter<T, Name>(PhantomData<(T, Name)>);
+impl<T, Name> Get<Option<H256>> for NamedMessageRootGetter<T, Name>
+where
+	T: Config + darwinia_evm::Config,
+	Name: Get<CommitmentContractName>,
+{
+	fn get() -> Option<H256> {
+		<MessageRootGetter<T>>::named(&Name::get())
+	}
+}
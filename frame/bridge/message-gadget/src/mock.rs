@@ -26,10 +26,10 @@ use scale_info::TypeInfo;
 // --- paritytech ---
 use fp_evm::{FeeCalculator, GenesisAccount};
 use frame_support::{
-	traits::{Everything, FindAuthor, GenesisBuild, WithdrawReasons},
+	traits::{Everything, FindAuthor, GenesisBuild, OnFinalize, WithdrawReasons},
 	ConsensusEngineId,
 };
-use frame_system::{mocking::*, RawOrigin};
+use frame_system::{mocking::*, EnsureRoot, RawOrigin};
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
 	testing::Header,
@@ -193,6 +193,8 @@ impl darwinia_evm::Config for Test {
 	type BlockHashMapping = SubstrateBlockHashMapping<Self>;
 	type CallOrigin = EnsureAddressRoot<Self::AccountId>;
 	type ChainId = ();
+	type CreateOrigin = ();
+	type DeployerAllowlistOrigin = EnsureRoot<Self::AccountId>;
 	type Event = Event;
 	type FeeCalculator = FixedGasPrice;
 	type FindAuthor = FindAuthorTruncated;
@@ -204,9 +206,24 @@ impl darwinia_evm::Config for Test {
 	type PrecompilesValue = ();
 	type RingBalanceAdapter = MockBalanceAdapter<Self>;
 	type Runner = Runner<Self>;
+	type StorageDepositCurrency = Ring;
+	type StorageDepositPerByte = ();
 }
 
-impl Config for Test {}
+frame_support::parameter_types! {
+	pub const CommitmentSelector: [u8; 4] = [19, 3, 164, 132];
+	pub const CommitmentGasLimit: u64 = 1_000_000_000_000;
+	pub CommitmentCaller: H160 = H160::default();
+	pub static ExpectedCodeHash: Option<H256> = None;
+}
+impl Config for Test {
+	type CommitmentCaller = CommitmentCaller;
+	type CommitmentGasLimit = CommitmentGasLimit;
+	type CommitmentSelector = CommitmentSelector;
+	type Event = Event;
+	type ExpectedCodeHash = ExpectedCodeHash;
+	type ManagerOrigin = EnsureRoot<AccountId32>;
+}
 
 frame_support::construct_runtime! {
 	pub enum Test where
@@ -219,7 +236,7 @@ frame_support::construct_runtime! {
 		Ring: darwinia_balances::<Instance1>::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Kton: darwinia_balances::<Instance2>::{Pallet, Call, Storage, Config<T>, Event<T>},
 		EVM: darwinia_evm::{Pallet, Call, Storage, Config, Event<T>},
-		MessageGadget: darwinia_message_gadget::{Pallet, Call, Storage, Config},
+		MessageGadget: darwinia_message_gadget::{Pallet, Call, Storage, Config, Event<T>},
 	}
 }
 
@@ -246,36 +263,50 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
 #[cfg(test)]
 mod tests {
+	// --- paritytech ---
+	use frame_support::{assert_noop, assert_ok};
+	// --- darwinia-network ---
 	use super::*;
 
+	fn deploy_commitment_contract() -> H160 {
+		// pragma solidity ^0.8.0;
+		//
+		// contract MessageRootGetter {
+		//     function commitment() public returns (bool) {
+		//         return true;
+		//     }
+		// }
+		const CONTRACT_CODE: &str = "0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c80631303a48414602d575b600080fd5b60336047565b604051603e9190605d565b60405180910390f35b60006001905090565b6057816076565b82525050565b6000602082019050607060008301846050565b92915050565b6000811515905091905056fea26469706673582212205edcbb73cc70f096b015d00b65ed893df280a01c9e90e964e8bb39957d6d3c9d64736f6c63430008070033";
+		let res = <Test as darwinia_evm::Config>::Runner::create(
+			H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+			hex2bytes(CONTRACT_CODE).unwrap(),
+			U256::zero(),
+			U256::from(300_000_000).low_u64(),
+			Some(<Test as darwinia_evm::Config>::FeeCalculator::min_gas_price()),
+			None,
+			Some(U256::from(1)),
+			vec![],
+			true,
+			<Test as darwinia_evm::Config>::config(),
+		);
+
+		res.unwrap().value
+	}
+
 	#[test]
 	fn test_get_return_something() {
 		new_test_ext().execute_with(|| {
-			// pragma solidity ^0.8.0;
-			//
-			// contract MessageRootGetter {
-			//     function commitment() public returns (bool) {
-			//         return true;
-			//     }
-			// }
-			const CONTRACT_CODE: &str = "0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c80631303a48414602d575b600080fd5b60336047565b604051603e9190605d565b60405180910390f35b60006001905090565b6057816076565b82525050565b6000602082019050607060008301846050565b92915050565b6000811515905091905056fea26469706673582212205edcbb73cc70f096b015d00b65ed893df280a01c9e90e964e8bb39957d6d3c9d64736f6c63430008070033";
-			let res = <Test as darwinia_evm::Config>::Runner::create(
-				H160::from_str("1000000000000000000000000000000000000001").unwrap(),
-				hex2bytes(CONTRACT_CODE).unwrap(),
-				U256::zero(),
-				U256::from(300_000_000).low_u64(),
-				Some(<Test as darwinia_evm::Config>::FeeCalculator::min_gas_price()),
-				None,
-				Some(U256::from(1)),
-				vec![],
-				true,
-				<Test as darwinia_evm::Config>::config(),
-			);
-			let contract_address = res.unwrap().value;
+			let contract_address = deploy_commitment_contract();
 			CommitmentContract::<Test>::put(contract_address);
 
 			assert_eq!(MessageGadget::commitment_contract(), contract_address);
-			assert_eq!(MessageRootGetter::<Test>::get(), Some(H256::from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1])));
+			assert_eq!(
+				MessageRootGetter::<Test>::get(),
+				Some(H256::from_slice(&[
+					0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+					0, 0, 0, 0, 0, 1
+				]))
+			);
 		});
 	}
 
@@ -285,4 +316,174 @@ mod tests {
 			assert_eq!(MessageRootGetter::<Test>::get(), None);
 		});
 	}
+	#[test]
+	fn test_cache_message_root_per_block() {
+		new_test_ext().execute_with(|| {
+			let contract_address = deploy_commitment_contract();
+			let message_root = Some(H256::from_low_u64_be(1));
+			let fake_message_root = Some(H256::repeat_byte(2));
+
+			System::set_block_number(1);
+			assert_eq!(MessageRootGetter::<Test>::get(), None);
+			assert_eq!(CachedMessageRoots::<Test>::get(H160::default()), Some((1, None)));
+
+			// Each commitment contract has its own cache entry.
+			CommitmentContract::<Test>::put(contract_address);
+			assert_eq!(MessageRootGetter::<Test>::get(), message_root);
+			assert_eq!(CachedMessageRoots::<Test>::get(contract_address), Some((1, message_root)));
+			assert_eq!(CachedMessageRoots::<Test>::get(H160::default()), Some((1, None)));
+
+			// Cached, the EVM call won't be executed again in the same block.
+			CachedMessageRoots::<Test>::insert(contract_address, (1, fake_message_root));
+			assert_eq!(MessageRootGetter::<Test>::get(), fake_message_root);
+
+			// All the entries are cleared at the end of the block.
+			MessageGadget::on_finalize(1);
+			assert!(CachedMessageRoots::<Test>::get(contract_address).is_none());
+			assert!(CachedMessageRoots::<Test>::get(H160::default()).is_none());
+			assert_eq!(CachedMessageRoots::<Test>::iter().count(), 0);
+
+			// An entry cached in the previous block is expired.
+			CachedMessageRoots::<Test>::insert(contract_address, (1, fake_message_root));
+			System::set_block_number(2);
+			assert_eq!(MessageRootGetter::<Test>::get(), message_root);
+			assert_eq!(CachedMessageRoots::<Test>::get(contract_address), Some((2, message_root)));
+		});
+	}
+
+	#[test]
+	fn test_message_root_weight() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(
+				MessageRootWeight::<Test>::get(),
+				<() as darwinia_evm::GasWeightMapping>::gas_to_weight(CommitmentGasLimit::get())
+			);
+		});
+	}
+	#[test]
+	fn test_genesis_config() {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		let commitment_contract = H160::repeat_byte(1);
+		let named_commitment_contract = H160::repeat_byte(2);
+
+		<darwinia_message_gadget::GenesisConfig as GenesisBuild<Test>>::assimilate_storage(
+			&darwinia_message_gadget::GenesisConfig {
+				commitment_contract,
+				commitment_contracts: vec![(*b"lane", named_commitment_contract)],
+			},
+			&mut t,
+		)
+		.unwrap();
+		sp_io::TestExternalities::from(t).execute_with(|| {
+			assert_eq!(MessageGadget::commitment_contract(), commitment_contract);
+			assert_eq!(
+				MessageGadget::named_commitment_contract(b"lane"),
+				Some(named_commitment_contract)
+			);
+		});
+	}
+
+	#[test]
+	fn test_set_commitment_contract() {
+		new_test_ext().execute_with(|| {
+			let commitment_contract = deploy_commitment_contract();
+
+			System::set_block_number(1);
+			assert!(MessageGadget::set_commitment_contract(
+				Origin::signed(Default::default()),
+				commitment_contract
+			)
+			.is_err());
+			assert!(
+				MessageGadget::set_commitment_contract(Origin::root(), commitment_contract).is_ok()
+			);
+			assert_eq!(MessageGadget::commitment_contract(), commitment_contract);
+			assert_eq!(
+				System::events().pop().unwrap().event,
+				Event::MessageGadget(darwinia_message_gadget::Event::CommitmentContractSet {
+					previous: H160::default(),
+					new: commitment_contract
+				})
+			);
+
+			assert!(MessageGadget::set_named_commitment_contract(
+				Origin::root(),
+				*b"lane",
+				Some(commitment_contract)
+			)
+			.is_ok());
+			assert_eq!(
+				MessageGadget::named_commitment_contract(b"lane"),
+				Some(commitment_contract)
+			);
+			assert_eq!(
+				System::events().pop().unwrap().event,
+				Event::MessageGadget(darwinia_message_gadget::Event::NamedCommitmentContractSet {
+					name: *b"lane",
+					previous: None,
+					new: Some(commitment_contract)
+				})
+			);
+
+			assert!(MessageGadget::set_named_commitment_contract(Origin::root(), *b"lane", None)
+				.is_ok());
+			assert!(MessageGadget::named_commitment_contract(b"lane").is_none());
+			assert_eq!(
+				System::events().pop().unwrap().event,
+				Event::MessageGadget(darwinia_message_gadget::Event::NamedCommitmentContractSet {
+					name: *b"lane",
+					previous: Some(commitment_contract),
+					new: None
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn test_named_message_root_getter() {
+		frame_support::parameter_types! {
+			pub const Lane: CommitmentContractName = *b"lane";
+		}
+
+		new_test_ext().execute_with(|| {
+			let contract_address = deploy_commitment_contract();
+
+			assert_eq!(NamedMessageRootGetter::<Test, Lane>::get(), None);
+
+			CommitmentContracts::<Test>::insert(b"lane", contract_address);
+			assert_eq!(NamedMessageRootGetter::<Test, Lane>::get(), Some(H256::from_low_u64_be(1)));
+			// The default one is not affected.
+			assert_eq!(MessageRootGetter::<Test>::get(), None);
+		});
+	}
+	#[test]
+	fn test_verify_commitment_contract() {
+		new_test_ext().execute_with(|| {
+			let commitment_contract = deploy_commitment_contract();
+
+			assert_noop!(
+				MessageGadget::set_commitment_contract(Origin::root(), H160::repeat_byte(1)),
+				<Error<Test>>::ContractCodeEmpty
+			);
+			assert_noop!(
+				MessageGadget::set_named_commitment_contract(
+					Origin::root(),
+					*b"lane",
+					Some(H160::repeat_byte(1))
+				),
+				<Error<Test>>::ContractCodeEmpty
+			);
+
+			EXPECTED_CODE_HASH.with(|v| *v.borrow_mut() = Some(H256::zero()));
+			assert_noop!(
+				MessageGadget::set_commitment_contract(Origin::root(), commitment_contract),
+				<Error<Test>>::CodeHashMismatch
+			);
+
+			let code_hash = H256(hashing::keccak_256(&EVM::account_codes(commitment_contract)));
+			EXPECTED_CODE_HASH.with(|v| *v.borrow_mut() = Some(code_hash));
+			assert_ok!(MessageGadget::set_commitment_contract(Origin::root(), commitment_contract));
+			assert_eq!(MessageGadget::commitment_contract(), commitment_contract);
+		});
+	}
 }
@@ -68,7 +68,7 @@ use sp_runtime::{
 };
 use sp_std::{marker::PhantomData, prelude::*};
 // --- darwinia-network ---
-use darwinia_evm::{BlockHashMapping, GasWeightMapping, Runner};
+use darwinia_evm::{BlockHashMapping, EnsureCreateOrigin, GasWeightMapping, Runner};
 use darwinia_support::evm::recover_signer;
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -461,6 +461,15 @@ impl<T: Config> Pallet<T> {
 			.into());
 		}
 
+		if transaction_data.action == TransactionAction::Create
+			&& T::CreateOrigin::check_create_origin(&origin).is_err()
+		{
+			return Err(InvalidTransaction::Custom(
+				TransactionValidationError::CreateOriginNotAllowed as u8,
+			)
+			.into());
+		}
+
 		if let Some(chain_id) = transaction_data.chain_id {
 			if chain_id != T::ChainId::get() {
 				return Err(InvalidTransaction::Custom(
@@ -879,6 +888,7 @@ enum TransactionValidationError {
 	InvalidChainId,
 	InvalidSignature,
 	InvalidGasLimit,
+	CreateOriginNotAllowed,
 }
 /// Returns the Ethereum block hash by number.
 pub struct EthereumBlockHashMapping<T>(PhantomData<T>);
@@ -31,7 +31,7 @@ use frame_support::{
 	weights::GetDispatchInfo,
 	ConsensusEngineId,
 };
-use frame_system::mocking::*;
+use frame_system::{mocking::*, EnsureRoot};
 use pallet_evm_precompile_simple::{ECRecover, Identity, Ripemd160, Sha256};
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
@@ -45,6 +45,7 @@ use crate::{self as darwinia_ethereum, adapter::*, *};
 use bp_message_dispatch::{CallValidate, IntoDispatchOrigin as IntoDispatchOriginT};
 use darwinia_evm::{
 	runner::stack::Runner, CurrencyAdapt, EVMCurrencyAdapter, EnsureAddressTruncated,
+	EnsureAllowedDeployer, EnsureCreateOrigin,
 };
 use darwinia_support::evm::{DeriveEthereumAddress, DeriveSubstrateAddress};
 
@@ -202,12 +203,25 @@ frame_support::parameter_types! {
 	pub const ChainId: u64 = 42;
 	pub const BlockGasLimit: U256 = U256::MAX;
 	pub PrecompilesValue: MockPrecompiles<Test> = MockPrecompiles::<_>::new();
+	pub static PermissionedCreate: bool = false;
+}
+pub struct MockCreateOrigin;
+impl EnsureCreateOrigin<Test> for MockCreateOrigin {
+	fn check_create_origin(address: &H160) -> Result<(), darwinia_evm::Error<Test>> {
+		if PermissionedCreate::get() {
+			<EnsureAllowedDeployer as EnsureCreateOrigin<Test>>::check_create_origin(address)
+		} else {
+			Ok(())
+		}
+	}
 }
 impl darwinia_evm::Config for Test {
 	type BlockGasLimit = BlockGasLimit;
 	type BlockHashMapping = EthereumBlockHashMapping<Self>;
 	type CallOrigin = EnsureAddressTruncated<Self::AccountId>;
 	type ChainId = ChainId;
+	type CreateOrigin = MockCreateOrigin;
+	type DeployerAllowlistOrigin = EnsureRoot<Self::AccountId>;
 	type Event = Event;
 	type FeeCalculator = FixedGasPrice;
 	type FindAuthor = FindAuthorTruncated;
@@ -219,6 +233,8 @@ impl darwinia_evm::Config for Test {
 	type PrecompilesValue = PrecompilesValue;
 	type RingBalanceAdapter = CurrencyAdapter<Self, Ring, RingRemainBalance>;
 	type Runner = Runner<Self>;
+	type StorageDepositCurrency = Ring;
+	type StorageDepositPerByte = ();
 }
 
 impl darwinia_ethereum::Config for Test {
@@ -675,3 +691,7 @@ pub fn contract_address(sender: H160, nonce: u64) -> H160 {
 pub fn storage_address(sender: H160, slot: H256) -> H256 {
 	H256::from_slice(&Keccak256::digest([&H256::from(sender)[..], &slot[..]].concat().as_slice()))
 }
+
+pub fn permissioned_create(enabled: bool) {
+	PERMISSIONED_CREATE.with(|v| *v.borrow_mut() = enabled);
+}
@@ -154,6 +154,43 @@ fn transaction_with_invalid_chain_id_should_fail_in_block() {
 	});
 }
 
+#[test]
+fn contract_creation_should_respect_deployer_allowlist() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+	let erc20_address = contract_address(alice.address, 0);
+
+	ext.execute_with(|| {
+		permissioned_create(true);
+
+		let call =
+			crate::Call::<Test>::transact { transaction: legacy_erc20_creation_transaction(alice) };
+		let source = call.check_self_contained().unwrap().unwrap();
+
+		assert_err!(
+			call.validate_self_contained(&source).unwrap(),
+			InvalidTransaction::Custom(
+				crate::TransactionValidationError::CreateOriginNotAllowed as u8
+			)
+		);
+		assert!(Ethereum::execute(
+			alice.address,
+			&legacy_erc20_creation_transaction(alice).into(),
+			None
+		)
+		.is_err());
+
+		assert_ok!(EVM::add_deployer(Origin::root(), alice.address));
+		assert_ok!(call.validate_self_contained(&source).unwrap());
+		assert_ok!(Ethereum::execute(
+			alice.address,
+			&legacy_erc20_creation_transaction(alice).into(),
+			None
+		));
+		assert_ne!(EVM::account_codes(erc20_address).len(), 0);
+	});
+}
+
 #[test]
 fn contract_constructor_should_get_executed() {
 	let (pairs, mut ext) = new_test_ext(1);
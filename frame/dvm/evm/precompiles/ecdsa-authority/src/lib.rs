@@ -0,0 +1,108 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod tests;
+
+// --- core ---
+use core::marker::PhantomData;
+// --- darwinia-network ---
+use darwinia_ecdsa_authority::primitives::Commitment;
+use darwinia_evm_precompile_utils::{prelude::*, revert, PrecompileHelper};
+// --- paritytech ---
+use fp_evm::{
+	Context, ExitRevert, ExitSucceed, Precompile, PrecompileFailure, PrecompileOutput,
+	PrecompileResult,
+};
+use sp_core::{ecdsa::Signature, H256, U256};
+use sp_std::{convert::TryFrom, vec::Vec};
+
+/// The gas cost of the `ecrecover`, charged for each signature.
+const ECRECOVER_GAS: u64 = 3_000;
+
+#[selector]
+enum Action {
+	VerifyCommitment = "verify_commitment(uint32,bytes32,uint256,address[],bytes[])",
+}
+
+pub struct EcdsaAuthority<T>(PhantomData<T>);
+
+impl<T> Precompile for EcdsaAuthority<T>
+where
+	T: darwinia_evm::Config + darwinia_ecdsa_authority::Config,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		let mut helper = PrecompileHelper::<T>::new(input, target_gas, context, is_static);
+		let (selector, _) = helper.split_input()?;
+		let action = Action::from_u32(selector)?;
+
+		// Check state modifiers
+		helper.check_state_modifier(StateMutability::View)?;
+
+		let output = match action {
+			Action::VerifyCommitment => {
+				let mut reader = helper.reader()?;
+				reader.expect_arguments(5)?;
+				let block_number = reader.read::<u32>()?;
+				let message_root = reader.read::<H256>()?;
+				let nonce = reader.read::<U256>()?;
+				let signers = reader.read::<Vec<Address>>()?;
+				let signatures = reader.read::<Vec<Bytes>>()?;
+
+				if signers.len() != signatures.len() {
+					return Err(revert("Signers and signatures length mismatch"));
+				}
+
+				// Read the authorities and the nonce.
+				helper.record_db_gas(2, 0)?;
+				// Charge all the `ecrecover`s before verifying any of them.
+				helper.record_gas(ECRECOVER_GAS.saturating_mul(signatures.len() as u64))?;
+
+				let signatures = signers
+					.into_iter()
+					.zip(signatures.into_iter())
+					.map(|(signer, signature)| {
+						Signature::try_from(signature.as_bytes())
+							.map(|signature| (signer.into(), signature))
+							.map_err(|_| revert("Invalid signature"))
+					})
+					.collect::<EvmResult<Vec<_>>>()?;
+
+				nonce <= U256::from(u32::MAX)
+					&& <darwinia_ecdsa_authority::Pallet<T>>::verify_commitment(
+						&Commitment { block_number, message_root, nonce: nonce.low_u32() },
+						&signatures,
+					)
+			},
+		};
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: helper.used_gas(),
+			output: EvmDataWriter::new().write(output).build(),
+			logs: Default::default(),
+		})
+	}
+}
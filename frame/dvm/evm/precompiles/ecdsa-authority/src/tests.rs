@@ -0,0 +1,358 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+//! Test utilities
+
+// --- crates.io ---
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+// --- paritytech ---
+use fp_evm::{Context, ExitError, FeeCalculator, PrecompileFailure, PrecompileResult};
+use frame_support::{
+	traits::{ConstU32, ConstU64, Everything, FindAuthor, GenesisBuild, OnInitialize},
+	weights::Weight,
+	ConsensusEngineId,
+};
+use frame_system::{mocking::*, offchain::SendTransactionTypes, EnsureRoot};
+use sp_core::{H160, H256, U256};
+use sp_runtime::{
+	testing::{Header, TestXt},
+	traits::{BlakeTwo256, IdentityLookup},
+	transaction_validity::TransactionPriority,
+	AccountId32, Perbill, RuntimeDebug,
+};
+use sp_std::prelude::*;
+// --- darwinia-network ---
+use crate::*;
+use darwinia_ecdsa_authority::primitives::{Feed, MessageRootFeeds};
+use darwinia_ethereum::{
+	adapter::{CurrencyAdapter, KtonRemainBalance, RingRemainBalance},
+	EthereumBlockHashMapping, IntermediateStateRoot,
+};
+use darwinia_evm::{runner::stack::Runner, EVMCurrencyAdapter, EnsureAddressTruncated};
+use darwinia_evm_precompile_utils::test_helper::{address_build, AccountInfo};
+use darwinia_support::evm::DeriveSubstrateAddress;
+
+type Block = MockBlock<Test>;
+type UncheckedExtrinsic = MockUncheckedExtrinsic<Test>;
+type Balance = u64;
+
+darwinia_support::impl_test_account_data! {}
+
+impl frame_system::Config for Test {
+	type AccountData = AccountData<Balance>;
+	type AccountId = AccountId32;
+	type BaseCallFilter = Everything;
+	type BlockHashCount = ();
+	type BlockLength = ();
+	type BlockNumber = u64;
+	type BlockWeights = ();
+	type Call = Call;
+	type DbWeight = ();
+	type Event = Event;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type Header = Header;
+	type Index = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type MaxConsumers = ConstU32<16>;
+	type OnKilledAccount = ();
+	type OnNewAccount = ();
+	type OnSetCode = ();
+	type Origin = Origin;
+	type PalletInfo = PalletInfo;
+	type SS58Prefix = ();
+	type SystemWeightInfo = ();
+	type Version = ();
+}
+
+frame_support::parameter_types! {
+	pub const ExistentialDeposit: u64 = 0;
+}
+impl darwinia_balances::Config<RingInstance> for Test {
+	type AccountStore = System;
+	type Balance = Balance;
+	type BalanceInfo = AccountData<Balance>;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+impl darwinia_balances::Config<KtonInstance> for Test {
+	type AccountStore = System;
+	type Balance = Balance;
+	type BalanceInfo = AccountData<Balance>;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+frame_support::parameter_types! {
+	pub const MinimumPeriod: u64 = 6000 / 2;
+}
+impl pallet_timestamp::Config for Test {
+	type MinimumPeriod = MinimumPeriod;
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type WeightInfo = ();
+}
+
+pub struct FixedGasPrice;
+impl FeeCalculator for FixedGasPrice {
+	fn min_gas_price() -> U256 {
+		1.into()
+	}
+}
+pub struct FindAuthorTruncated;
+impl FindAuthor<H160> for FindAuthorTruncated {
+	fn find_author<'a, I>(_digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		Some(address_build(0).address)
+	}
+}
+pub struct HashedConverter;
+impl DeriveSubstrateAddress<AccountId32> for HashedConverter {
+	fn derive_substrate_address(address: &H160) -> AccountId32 {
+		let mut raw_account = [0u8; 32];
+		raw_account[0..20].copy_from_slice(&address[..]);
+		raw_account.into()
+	}
+}
+frame_support::parameter_types! {
+	pub const ChainId: u64 = 42;
+	pub const BlockGasLimit: U256 = U256::MAX;
+}
+impl darwinia_evm::Config for Test {
+	type BlockGasLimit = BlockGasLimit;
+	type BlockHashMapping = EthereumBlockHashMapping<Self>;
+	type CallOrigin = EnsureAddressTruncated<Self::AccountId>;
+	type ChainId = ChainId;
+	type CreateOrigin = ();
+	type DeployerAllowlistOrigin = EnsureRoot<Self::AccountId>;
+	type Event = Event;
+	type FeeCalculator = FixedGasPrice;
+	type FindAuthor = FindAuthorTruncated;
+	type GasWeightMapping = ();
+	type IntoAccountId = HashedConverter;
+	type KtonBalanceAdapter = CurrencyAdapter<Self, Kton, KtonRemainBalance>;
+	type OnChargeTransaction = EVMCurrencyAdapter<()>;
+	type PrecompilesType = ();
+	type PrecompilesValue = ();
+	type RingBalanceAdapter = CurrencyAdapter<Self, Ring, RingRemainBalance>;
+	type Runner = Runner<Self>;
+	type StorageDepositCurrency = Ring;
+	type StorageDepositPerByte = ();
+}
+
+impl darwinia_ethereum::Config for Test {
+	type Event = Event;
+	type StateRoot = IntermediateStateRoot;
+}
+
+impl<C> SendTransactionTypes<C> for Test
+where
+	Call: From<C>,
+{
+	type Extrinsic = TestXt<Call, ()>;
+	type OverarchingCall = Call;
+}
+const FEED: Feed = Feed { target_chain_id: 1, source: [0; 4] };
+pub struct MockMessageRoots;
+impl MessageRootFeeds for MockMessageRoots {
+	fn feeds() -> Vec<Feed> {
+		vec![FEED]
+	}
+
+	fn message_root(_: &Feed) -> Option<H256> {
+		Some(H256::repeat_byte(1))
+	}
+
+	fn message_root_weight(_: &Feed) -> Weight {
+		0
+	}
+}
+frame_support::parameter_types! {
+	pub const EcdsaAuthorityChainId: &'static [u8] = b"46";
+	pub const SignThreshold: Perbill = Perbill::from_percent(60);
+	pub const UnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+}
+impl darwinia_ecdsa_authority::Config for Test {
+	type ChainId = EcdsaAuthorityChainId;
+	type Event = Event;
+	type MaxAuthorities = ConstU32<3>;
+	type MaxAuthoritiesChangePendingPeriod = ConstU64<8>;
+	type MaxPendingPeriod = ConstU64<5>;
+	type MaxSignatureRoundRecords = ConstU32<3>;
+	type MessageRoots = MockMessageRoots;
+	type OnSignatureRound = ();
+	type SignThreshold = SignThreshold;
+	type SyncInterval = ConstU64<1>;
+	type UnsignedPriority = UnsignedPriority;
+	type WeightInfo = ();
+}
+
+frame_support::construct_runtime! {
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
+		Ring: darwinia_balances::<Instance1>::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Kton: darwinia_balances::<Instance2>::{Pallet, Call, Storage, Config<T>, Event<T>},
+		EVM: darwinia_evm::{Pallet, Call, Storage, Config, Event<T>},
+		Ethereum: darwinia_ethereum::{Pallet, Call, Storage, Config, Event<T>, Origin},
+		EcdsaAuthority: darwinia_ecdsa_authority::{Pallet, Call, Storage, Config, Event<T>},
+	}
+}
+
+fn new_test_ext(authorities: &[AccountInfo]) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	<darwinia_ecdsa_authority::GenesisConfig as GenesisBuild<Test>>::assimilate_storage(
+		&darwinia_ecdsa_authority::GenesisConfig {
+			authorities: authorities.iter().map(|a| a.address).collect(),
+		},
+		&mut t,
+	)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		<EcdsaAuthority as OnInitialize<_>>::on_initialize(1);
+	});
+
+	ext
+}
+
+fn sign(account: &AccountInfo, message: &[u8; 32]) -> Bytes {
+	let secret_key = libsecp256k1::SecretKey::parse_slice(&account.private_key[..]).unwrap();
+	let (signature, recovery_id) =
+		libsecp256k1::sign(&libsecp256k1::Message::parse(message), &secret_key);
+	let mut signature = signature.serialize().to_vec();
+
+	signature.push(recovery_id.serialize());
+
+	Bytes(signature)
+}
+
+fn verify_commitment(
+	signers: Vec<H160>,
+	signatures: Vec<Bytes>,
+	target_gas: Option<u64>,
+) -> PrecompileResult {
+	let (commitment, _, _) = EcdsaAuthority::new_message_root_to_sign(FEED).unwrap();
+	let input = EvmDataWriter::new_with_selector(Action::VerifyCommitment)
+		.write(commitment.block_number)
+		.write(commitment.message_root)
+		.write(U256::from(commitment.nonce))
+		.write(signers.into_iter().map(Address).collect::<Vec<_>>())
+		.write(signatures)
+		.build();
+	let context = Context {
+		address: Default::default(),
+		caller: Default::default(),
+		apparent_value: 0.into(),
+	};
+
+	<crate::EcdsaAuthority<Test> as Precompile>::execute(&input, target_gas, &context, false)
+}
+
+fn message() -> [u8; 32] {
+	EcdsaAuthority::new_message_root_to_sign(FEED).unwrap().1
+}
+
+#[test]
+fn verify_commitment_should_work() {
+	let authorities = (1..=3).map(address_build).collect::<Vec<_>>();
+
+	new_test_ext(&authorities).execute_with(|| {
+		let message = message();
+		let output = verify_commitment(
+			vec![authorities[0].address, authorities[1].address],
+			vec![sign(&authorities[0], &message), sign(&authorities[1], &message)],
+			Some(ECRECOVER_GAS * 2),
+		)
+		.unwrap();
+
+		assert_eq!(output.output, EvmDataWriter::new().write(true).build());
+		assert_eq!(output.cost, ECRECOVER_GAS * 2);
+	});
+}
+
+#[test]
+fn verify_commitment_should_fail_below_threshold() {
+	let authorities = (1..=3).map(address_build).collect::<Vec<_>>();
+	let outsider = address_build(4);
+
+	new_test_ext(&authorities).execute_with(|| {
+		let message = message();
+		let output = verify_commitment(
+			vec![authorities[0].address, outsider.address, authorities[0].address],
+			vec![
+				sign(&authorities[0], &message),
+				sign(&outsider, &message),
+				sign(&authorities[0], &message),
+			],
+			None,
+		)
+		.unwrap();
+
+		assert_eq!(output.output, EvmDataWriter::new().write(false).build());
+		assert_eq!(output.cost, ECRECOVER_GAS * 3);
+	});
+}
+
+#[test]
+fn verify_commitment_should_charge_before_verifying() {
+	let authorities = (1..=3).map(address_build).collect::<Vec<_>>();
+
+	new_test_ext(&authorities).execute_with(|| {
+		let message = message();
+
+		assert!(matches!(
+			verify_commitment(
+				vec![authorities[0].address, authorities[1].address],
+				vec![sign(&authorities[0], &message), sign(&authorities[1], &message)],
+				Some(ECRECOVER_GAS * 2 - 1),
+			),
+			Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas })
+		));
+		// Run out of gas before decoding or verifying any of the invalid signatures.
+		assert!(matches!(
+			verify_commitment(
+				vec![H160::default(); 100],
+				vec![Bytes(vec![0; 64]); 100],
+				Some(ECRECOVER_GAS * 10),
+			),
+			Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas })
+		));
+	});
+}
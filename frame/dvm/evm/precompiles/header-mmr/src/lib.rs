@@ -0,0 +1,149 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod tests;
+
+// --- core ---
+use core::marker::PhantomData;
+// --- crates.io ---
+use codec::DecodeAll;
+// --- darwinia-network ---
+use darwinia_evm_precompile_utils::{prelude::*, revert, PrecompileHelper};
+use darwinia_header_mmr::{LeafDataProvider, VersionedLeaf};
+// --- paritytech ---
+use fp_evm::{Context, ExitSucceed, Precompile, PrecompileOutput, PrecompileResult};
+use sp_core::H256;
+use sp_std::{vec, vec::Vec};
+
+/// The gas cost of merging two MMR nodes, charged for each proof item.
+const MERGE_GAS: u64 = 100;
+
+#[selector]
+enum Action {
+	VerifyHeaderProof = "verifyHeaderProof(uint64,bytes32,bytes32[])",
+	VerifyLeafProof = "verifyLeafProof(uint64,bytes,bytes32[])",
+}
+
+type LeafOf<T> = VersionedLeaf<
+	H256,
+	<<T as darwinia_header_mmr::Config>::LeafData as LeafDataProvider<
+		<T as frame_system::Config>::Hash,
+	>>::LeafData,
+>;
+
+pub struct HeaderMmr<T>(PhantomData<T>);
+
+impl<T> Precompile for HeaderMmr<T>
+where
+	T: darwinia_evm::Config + darwinia_header_mmr::Config + frame_system::Config<Hash = H256>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		let mut helper = PrecompileHelper::<T>::new(input, target_gas, context, is_static);
+		let (selector, _) = helper.split_input()?;
+		let action = Action::from_u32(selector)?;
+
+		// Check state modifiers
+		helper.check_state_modifier(StateMutability::View)?;
+
+		let output = match action {
+			Action::VerifyHeaderProof => {
+				let mut reader = helper.reader()?;
+				reader.expect_arguments(3)?;
+				let block_number = reader.read::<u64>()?;
+				let block_hash = reader.read::<H256>()?;
+				let proof = reader.read::<Vec<H256>>()?;
+
+				// Read the leaf version, the MMR size and the peaks.
+				helper.record_db_gas(2 + peak_count::<T>(), 0)?;
+				// Charge the merges before verifying the proof.
+				helper.record_gas(MERGE_GAS.saturating_mul(proof.len() as u64))?;
+
+				// Only the `V0` leaf is the header hash itself.
+				if <darwinia_header_mmr::Pallet<T>>::leaf_version_of(block_number) != 0 {
+					return Err(revert("The leaf is not a header hash, use `verifyLeafProof`"));
+				}
+
+				verify::<T>(block_number, block_hash, proof)
+			},
+			Action::VerifyLeafProof => {
+				let mut reader = helper.reader()?;
+				reader.expect_arguments(3)?;
+				let block_number = reader.read::<u64>()?;
+				let leaf = reader.read::<Bytes>()?;
+				let proof = reader.read::<Vec<H256>>()?;
+
+				// Read the leaf version, the MMR size and the peaks.
+				helper.record_db_gas(2 + peak_count::<T>(), 0)?;
+				// Charge hashing the leaf and the merges before verifying the proof.
+				helper.record_gas(MERGE_GAS.saturating_mul(proof.len() as u64 + 1))?;
+
+				let leaf = <LeafOf<T>>::decode_all(&leaf.0).map_err(|_| revert("Invalid leaf"))?;
+
+				leaf.version() == <darwinia_header_mmr::Pallet<T>>::leaf_version_of(block_number)
+					&& verify::<T>(
+						block_number,
+						leaf.node::<<T as frame_system::Config>::Hashing>(),
+						proof,
+					)
+			},
+		};
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: helper.used_gas(),
+			output: EvmDataWriter::new().write(output).build(),
+			logs: Default::default(),
+		})
+	}
+}
+
+/// The number of the peaks read by `get_root`, which is the number of the ones in the leaf count.
+fn peak_count<T>() -> u64
+where
+	T: darwinia_header_mmr::Config,
+{
+	darwinia_header_mmr::leaf_count(<darwinia_header_mmr::Pallet<T>>::mmr_size()).count_ones()
+		as u64
+}
+
+/// Verify the proof against the current MMR root, which is `get_root`.
+fn verify<T>(block_number: u64, node: H256, proof: Vec<H256>) -> bool
+where
+	T: darwinia_header_mmr::Config + frame_system::Config<Hash = H256>,
+{
+	let mmr_size = <darwinia_header_mmr::Pallet<T>>::mmr_size();
+
+	<darwinia_header_mmr::Pallet<T>>::get_root()
+		.map(|root| {
+			<darwinia_header_mmr::Pallet<T>>::verify_proof(
+				root,
+				vec![(block_number, node)],
+				proof,
+				mmr_size,
+			)
+		})
+		.unwrap_or(false)
+}
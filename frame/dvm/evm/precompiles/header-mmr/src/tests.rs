@@ -0,0 +1,403 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+//! Test utilities
+
+// --- crates.io ---
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+// --- paritytech ---
+use fp_evm::{Context, ExitError, FeeCalculator, PrecompileFailure, PrecompileResult};
+use frame_support::{
+	traits::{ConstU32, Everything, FindAuthor, OnFinalize},
+	ConsensusEngineId,
+};
+use frame_system::{mocking::*, EnsureRoot};
+use sp_core::{
+	offchain::{testing::TestOffchainExt, OffchainDbExt, OffchainWorkerExt},
+	H160, H256, U256,
+};
+use sp_io::TestExternalities;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Header as _, IdentityLookup},
+	AccountId32, RuntimeDebug,
+};
+// --- darwinia-network ---
+use crate::*;
+use darwinia_ethereum::{
+	adapter::{CurrencyAdapter, KtonRemainBalance, RingRemainBalance},
+	EthereumBlockHashMapping, IntermediateStateRoot,
+};
+use darwinia_evm::{runner::stack::Runner, EVMCurrencyAdapter, EnsureAddressTruncated};
+use darwinia_evm_precompile_utils::test_helper::address_build;
+use darwinia_header_mmr::{LeafDataProvider, MmrProof, VersionedLeaf};
+use darwinia_support::evm::DeriveSubstrateAddress;
+
+type Block = MockBlock<Test>;
+type UncheckedExtrinsic = MockUncheckedExtrinsic<Test>;
+type Balance = u64;
+
+darwinia_support::impl_test_account_data! {}
+
+impl frame_system::Config for Test {
+	type AccountData = AccountData<Balance>;
+	type AccountId = AccountId32;
+	type BaseCallFilter = Everything;
+	type BlockHashCount = ();
+	type BlockLength = ();
+	type BlockNumber = u64;
+	type BlockWeights = ();
+	type Call = Call;
+	type DbWeight = ();
+	type Event = Event;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type Header = Header;
+	type Index = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type MaxConsumers = ConstU32<16>;
+	type OnKilledAccount = ();
+	type OnNewAccount = ();
+	type OnSetCode = ();
+	type Origin = Origin;
+	type PalletInfo = PalletInfo;
+	type SS58Prefix = ();
+	type SystemWeightInfo = ();
+	type Version = ();
+}
+
+frame_support::parameter_types! {
+	pub const ExistentialDeposit: u64 = 0;
+}
+impl darwinia_balances::Config<RingInstance> for Test {
+	type AccountStore = System;
+	type Balance = Balance;
+	type BalanceInfo = AccountData<Balance>;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+impl darwinia_balances::Config<KtonInstance> for Test {
+	type AccountStore = System;
+	type Balance = Balance;
+	type BalanceInfo = AccountData<Balance>;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+frame_support::parameter_types! {
+	pub const MinimumPeriod: u64 = 6000 / 2;
+}
+impl pallet_timestamp::Config for Test {
+	type MinimumPeriod = MinimumPeriod;
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type WeightInfo = ();
+}
+
+pub struct FixedGasPrice;
+impl FeeCalculator for FixedGasPrice {
+	fn min_gas_price() -> U256 {
+		1.into()
+	}
+}
+pub struct FindAuthorTruncated;
+impl FindAuthor<H160> for FindAuthorTruncated {
+	fn find_author<'a, I>(_digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		Some(address_build(0).address)
+	}
+}
+pub struct HashedConverter;
+impl DeriveSubstrateAddress<AccountId32> for HashedConverter {
+	fn derive_substrate_address(address: &H160) -> AccountId32 {
+		let mut raw_account = [0u8; 32];
+		raw_account[0..20].copy_from_slice(&address[..]);
+		raw_account.into()
+	}
+}
+frame_support::parameter_types! {
+	pub const ChainId: u64 = 42;
+	pub const BlockGasLimit: U256 = U256::MAX;
+}
+impl darwinia_evm::Config for Test {
+	type BlockGasLimit = BlockGasLimit;
+	type BlockHashMapping = EthereumBlockHashMapping<Self>;
+	type CallOrigin = EnsureAddressTruncated<Self::AccountId>;
+	type ChainId = ChainId;
+	type CreateOrigin = ();
+	type DeployerAllowlistOrigin = EnsureRoot<Self::AccountId>;
+	type Event = Event;
+	type FeeCalculator = FixedGasPrice;
+	type FindAuthor = FindAuthorTruncated;
+	type GasWeightMapping = ();
+	type IntoAccountId = HashedConverter;
+	type KtonBalanceAdapter = CurrencyAdapter<Self, Kton, KtonRemainBalance>;
+	type OnChargeTransaction = EVMCurrencyAdapter<()>;
+	type PrecompilesType = ();
+	type PrecompilesValue = ();
+	type RingBalanceAdapter = CurrencyAdapter<Self, Ring, RingRemainBalance>;
+	type Runner = Runner<Self>;
+	type StorageDepositCurrency = Ring;
+	type StorageDepositPerByte = ();
+}
+
+impl darwinia_ethereum::Config for Test {
+	type Event = Event;
+	type StateRoot = IntermediateStateRoot;
+}
+
+frame_support::parameter_types! {
+	pub static WithLeafData: bool = false;
+}
+pub struct MockLeafData;
+impl LeafDataProvider<H256> for MockLeafData {
+	type LeafData = (H256, u64);
+
+	fn leaf(parent_hash: H256) -> VersionedLeaf<H256, Self::LeafData> {
+		if WithLeafData::get() {
+			VersionedLeaf::V1((parent_hash, System::block_number()))
+		} else {
+			VersionedLeaf::V0(parent_hash)
+		}
+	}
+}
+impl darwinia_header_mmr::Config for Test {
+	type LeafData = MockLeafData;
+	type WeightInfo = ();
+
+	const INDEXING_PREFIX: &'static [u8] = b"header-mmr-";
+}
+
+frame_support::construct_runtime! {
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
+		Ring: darwinia_balances::<Instance1>::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Kton: darwinia_balances::<Instance2>::{Pallet, Call, Storage, Config<T>, Event<T>},
+		EVM: darwinia_evm::{Pallet, Call, Storage, Config, Event<T>},
+		Ethereum: darwinia_ethereum::{Pallet, Call, Storage, Config, Event<T>, Origin},
+		HeaderMmr: darwinia_header_mmr::{Pallet, Storage},
+	}
+}
+
+fn new_test_ext() -> TestExternalities {
+	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+}
+
+fn register_offchain_ext(ext: &mut TestExternalities) {
+	ext.persist_offchain_overlay();
+
+	let (offchain, _) = TestOffchainExt::with_offchain_db(ext.offchain_db());
+
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+}
+
+/// Finalize a new block on the `parent_hash`, return its hash.
+fn new_block(parent_hash: H256) -> H256 {
+	let number = System::block_number() + 1;
+
+	System::initialize(&number, &parent_hash, &Default::default());
+	<HeaderMmr as OnFinalize<_>>::on_finalize(number);
+	System::finalize().hash()
+}
+
+/// Finalize `n` blocks from the genesis, return the block hashes.
+///
+/// The hash of block `i` is pushed as the leaf `i` on the next block.
+fn run_to_block(n: u64) -> Vec<H256> {
+	let mut hashes = vec![H256::zero()];
+
+	for _ in 0..n {
+		hashes.push(new_block(hashes[hashes.len() - 1]));
+	}
+
+	hashes
+}
+
+fn verify_header_proof(
+	block_number: u64,
+	block_hash: H256,
+	proof: Vec<H256>,
+	target_gas: Option<u64>,
+) -> PrecompileResult {
+	let input = EvmDataWriter::new_with_selector(Action::VerifyHeaderProof)
+		.write(block_number)
+		.write(block_hash)
+		.write(proof)
+		.build();
+	let context = Context {
+		address: Default::default(),
+		caller: Default::default(),
+		apparent_value: 0.into(),
+	};
+
+	<crate::HeaderMmr<Test> as Precompile>::execute(&input, target_gas, &context, false)
+}
+
+fn verify_leaf_proof(block_number: u64, leaf: LeafOf<Test>, proof: Vec<H256>) -> PrecompileResult {
+	let input = EvmDataWriter::new_with_selector(Action::VerifyLeafProof)
+		.write(block_number)
+		.write(Bytes(leaf.encode()))
+		.write(proof)
+		.build();
+	let context = Context {
+		address: Default::default(),
+		caller: Default::default(),
+		apparent_value: 0.into(),
+	};
+
+	<crate::HeaderMmr<Test> as Precompile>::execute(&input, None, &context, false)
+}
+
+#[test]
+fn verify_header_proof_should_work() {
+	let mut ext = new_test_ext();
+	let hashes = ext.execute_with(|| run_to_block(5));
+
+	register_offchain_ext(&mut ext);
+	ext.execute_with(|| {
+		let mmr_size = HeaderMmr::mmr_size();
+		let MmrProof { leaf, proof, .. } = HeaderMmr::gen_proof(2, mmr_size).unwrap();
+		let cost = MERGE_GAS * proof.len() as u64;
+
+		assert_eq!(leaf, hashes[2]);
+
+		let output = verify_header_proof(2, hashes[2], proof.clone(), Some(cost)).unwrap();
+
+		assert_eq!(output.output, EvmDataWriter::new().write(true).build());
+		assert_eq!(output.cost, cost);
+
+		let output = verify_header_proof(2, hashes[3], proof, Some(cost)).unwrap();
+
+		assert_eq!(output.output, EvmDataWriter::new().write(false).build());
+		assert_eq!(output.cost, cost);
+	});
+}
+
+#[test]
+fn verify_header_proof_should_work_against_current_root() {
+	let mut ext = new_test_ext();
+	let mut hashes = ext.execute_with(|| run_to_block(5));
+
+	register_offchain_ext(&mut ext);
+	ext.execute_with(|| {
+		let MmrProof { proof, .. } = HeaderMmr::gen_proof(2, HeaderMmr::mmr_size()).unwrap();
+
+		assert_eq!(
+			verify_header_proof(2, hashes[2], proof.clone(), None).unwrap().output,
+			EvmDataWriter::new().write(true).build()
+		);
+
+		// The proof is stale once a new leaf is pushed.
+		hashes.push(new_block(hashes[hashes.len() - 1]));
+
+		assert_eq!(
+			verify_header_proof(2, hashes[2], proof, None).unwrap().output,
+			EvmDataWriter::new().write(false).build()
+		);
+	});
+}
+
+#[test]
+fn verify_header_proof_should_charge_before_verifying() {
+	let mut ext = new_test_ext();
+	let hashes = ext.execute_with(|| run_to_block(5));
+
+	register_offchain_ext(&mut ext);
+	ext.execute_with(|| {
+		let mmr_size = HeaderMmr::mmr_size();
+		let MmrProof { proof, .. } = HeaderMmr::gen_proof(2, mmr_size).unwrap();
+		let cost = MERGE_GAS * proof.len() as u64;
+
+		assert!(matches!(
+			verify_header_proof(2, hashes[2], proof, Some(cost - 1)),
+			Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas })
+		));
+		// Run out of gas before hashing any of the proof items.
+		assert!(matches!(
+			verify_header_proof(2, hashes[2], vec![H256::zero(); 10_000], Some(MERGE_GAS)),
+			Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas })
+		));
+	});
+}
+
+#[test]
+fn verify_leaf_proof_should_work() {
+	let mut ext = new_test_ext();
+	let hashes = ext.execute_with(|| {
+		let mut hashes = run_to_block(3);
+
+		WITH_LEAF_DATA.with(|v| *v.borrow_mut() = true);
+
+		for _ in 0..3 {
+			hashes.push(new_block(hashes[hashes.len() - 1]));
+		}
+
+		hashes
+	});
+
+	register_offchain_ext(&mut ext);
+	ext.execute_with(|| {
+		let mmr_size = HeaderMmr::mmr_size();
+		let true_output = EvmDataWriter::new().write(true).build();
+		let false_output = EvmDataWriter::new().write(false).build();
+
+		// The leaves of block 0 to 2 are `V0`, the header hashes.
+		let MmrProof { proof, .. } = HeaderMmr::gen_proof(2, mmr_size).unwrap();
+
+		assert_eq!(
+			verify_header_proof(2, hashes[2], proof.clone(), None).unwrap().output,
+			true_output
+		);
+		assert_eq!(
+			verify_leaf_proof(2, VersionedLeaf::V0(hashes[2]), proof).unwrap().output,
+			true_output
+		);
+
+		// Since block 3, the leaves are `V1`, which are pushed on block 4 to 6.
+		let MmrProof { leaf, proof, .. } = HeaderMmr::gen_proof(4, mmr_size).unwrap();
+		let v1_leaf = VersionedLeaf::V1((hashes[4], 5));
+
+		assert_eq!(leaf, v1_leaf.node::<BlakeTwo256>());
+		assert_eq!(verify_leaf_proof(4, v1_leaf, proof.clone()).unwrap().output, true_output);
+		// The `V1` leaf can't be proved as a header hash.
+		assert!(verify_header_proof(4, leaf, proof.clone(), None).is_err());
+		assert_eq!(
+			verify_leaf_proof(4, VersionedLeaf::V0(leaf), proof).unwrap().output,
+			false_output
+		);
+	});
+}
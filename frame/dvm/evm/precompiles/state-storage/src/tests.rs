@@ -35,7 +35,7 @@ use frame_support::{
 	weights::GetDispatchInfo,
 	Blake2_128Concat, ConsensusEngineId, PalletId, StorageHasher, Twox128,
 };
-use frame_system::mocking::*;
+use frame_system::{mocking::*, EnsureRoot};
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
 	testing::Header,
@@ -222,6 +222,8 @@ impl darwinia_evm::Config for Test {
 	type BlockHashMapping = EthereumBlockHashMapping<Self>;
 	type CallOrigin = EnsureAddressTruncated<Self::AccountId>;
 	type ChainId = ChainId;
+	type CreateOrigin = ();
+	type DeployerAllowlistOrigin = EnsureRoot<Self::AccountId>;
 	type Event = Event;
 	type FeeCalculator = FixedGasPrice;
 	type FindAuthor = FindAuthorTruncated;
@@ -233,6 +235,8 @@ impl darwinia_evm::Config for Test {
 	type PrecompilesValue = PrecompilesValue;
 	type RingBalanceAdapter = CurrencyAdapter<Self, Ring, RingRemainBalance>;
 	type Runner = Runner<Self>;
+	type StorageDepositCurrency = Ring;
+	type StorageDepositPerByte = ();
 }
 
 impl darwinia_ethereum::Config for Test {
@@ -104,23 +104,22 @@ impl<'a, T: darwinia_evm::Config> PrecompileHelper<'a, T> {
 		.ok_or_else(|| revert("Cost Overflow"))?;
 		let cost = reads_cost.checked_add(writes_cost).ok_or_else(|| revert("Cost Overflow"))?;
 
-		self.used_gas = self
-			.used_gas
-			.checked_add(cost)
-			.ok_or(PrecompileFailure::Error { exit_status: ExitError::OutOfGas })?;
-
-		match self.target_gas {
-			Some(gas_limit) if self.used_gas > gas_limit =>
-				Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas }),
-			_ => Ok(()),
-		}
+		self.record_gas(cost)
 	}
 
 	pub fn record_log_gas(&mut self, topics: usize, data_len: usize) -> EvmResult<()> {
 		let log_costs = log::log_costs(topics, data_len)?;
+
+		self.record_gas(log_costs)
+	}
+
+	/// Record the cost and fail with `OutOfGas` once the used gas exceeds the target gas.
+	///
+	/// Record the cost before doing the expensive work, so it is never done for free.
+	pub fn record_gas(&mut self, cost: u64) -> EvmResult<()> {
 		self.used_gas = self
 			.used_gas
-			.checked_add(log_costs)
+			.checked_add(cost)
 			.ok_or(PrecompileFailure::Error { exit_status: ExitError::OutOfGas })?;
 
 		match self.target_gas {
@@ -0,0 +1,41 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition required by EVM RPC extensions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// --- paritytech ---
+use sp_core::{H160, H256};
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait EvmStorageApi {
+		/// Get the number of storage slots owned by the `address`.
+		///
+		/// `None` means unknown, while the storage count is being backfilled.
+		fn storage_count(address: H160) -> Option<u32>;
+
+		/// Page through the storage of the `address`, at most `limit` slots starting after
+		/// `start_after`.
+		///
+		/// The slots are ordered by their hashed storage keys, pass the last returned index as
+		/// `start_after` to fetch the next page.
+		fn storage_paged(address: H160, start_after: Option<H256>, limit: u32) -> Vec<(H256, H256)>;
+	}
+}
@@ -40,29 +40,44 @@ pub use fp_evm::{
 #[cfg(feature = "std")]
 use std::collections::BTreeMap;
 // --- crates.io ---
+use codec::{Decode, Encode};
 use evm::{Config as EvmConfig, ExitError, ExitReason};
+use scale_info::TypeInfo;
 // --- paritytech ---
 use fp_evm::FeeCalculator;
 #[cfg(feature = "std")]
 use fp_evm::GenesisAccount;
 use frame_support::{
-	traits::{FindAuthor, WithdrawReasons},
+	log,
+	storage::StoragePrefixedMap,
+	traits::{Currency, FindAuthor, Get, NamedReservableCurrency, StorageVersion, WithdrawReasons},
 	weights::{PostDispatchInfo, Weight},
 };
 use frame_system::RawOrigin;
 use sp_core::{H160, H256, U256};
+use sp_io::KillStorageResult;
 use sp_runtime::{
-	traits::{BadOrigin, UniqueSaturatedInto},
-	SaturatedConversion,
+	traits::{BadOrigin, Saturating, UniqueSaturatedInto, Zero},
+	RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{marker::PhantomData, prelude::*};
 // --- darwinia-network ---
 use darwinia_support::evm::DeriveSubstrateAddress;
 
 pub type AccountId<T> = <T as frame_system::Config>::AccountId;
+pub type StorageDepositBalance<T> =
+	<<T as Config>::StorageDepositCurrency as Currency<AccountId<T>>>::Balance;
 
 static LONDON_CONFIG: EvmConfig = EvmConfig::london();
 
+/// The identifier of the storage deposit reserves.
+pub const STORAGE_DEPOSIT_ID: [u8; 8] = *b"da/evmsd";
+/// The bytes charged for a storage slot, which is the size of its index plus value.
+pub const STORAGE_SLOT_SIZE: u64 = 64;
+
+/// The current storage version.
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 #[frame_support::pallet]
 pub mod pallet {
 	// --- paritytech ---
@@ -83,6 +98,10 @@ pub mod pallet {
 
 		/// Allow the origin to call on behalf of given address.
 		type CallOrigin: EnsureAddressOrigin<Self::Origin>;
+		/// Allow the address to create contracts.
+		type CreateOrigin: EnsureCreateOrigin<Self>;
+		/// The origin which could manage the deployer allowlist.
+		type DeployerAllowlistOrigin: EnsureOrigin<Self::Origin>;
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		/// Chain ID of EVM.
@@ -112,6 +131,16 @@ pub mod pallet {
 		/// `pallet_transaction_payment`
 		type OnChargeTransaction: OnChargeEVMTransaction<Self>;
 
+		/// The currency in which the storage deposit is reserved.
+		type StorageDepositCurrency: NamedReservableCurrency<
+			Self::AccountId,
+			ReserveIdentifier = [u8; 8],
+		>;
+		/// The deposit reserved per byte of contract code and storage slots.
+		///
+		/// Zero disables the storage deposit.
+		type StorageDepositPerByte: Get<StorageDepositBalance<Self>>;
+
 		/// EVM config used in the Pallet.
 		fn config() -> &'static EvmConfig {
 			&LONDON_CONFIG
@@ -132,6 +161,16 @@ pub mod pallet {
 		/// A contract has been executed with errors. States are reverted with only gas fees
 		/// applied.
 		ExecutedFailed { address: H160 },
+		/// The storage of a removed contract has been cleared.
+		StorageRemoved { address: H160 },
+		/// Storage deposit has been reserved from the depositor of the contract.
+		StorageDepositReserved { contract: H160, depositor: H160, amount: StorageDepositBalance<T> },
+		/// Storage deposit has been refunded to the depositor of the contract.
+		StorageDepositRefunded { contract: H160, depositor: H160, amount: StorageDepositBalance<T> },
+		/// An address has been added to the deployer allowlist.
+		DeployerAdded { address: H160 },
+		/// An address has been removed from the deployer allowlist.
+		DeployerRemoved { address: H160 },
 	}
 
 	#[pallet::error]
@@ -148,6 +187,12 @@ pub mod pallet {
 		GasPriceTooLow,
 		/// Nonce is invalid
 		InvalidNonce,
+		/// The address is not allowed to create contracts.
+		CreateOriginNotAllowed,
+		/// The address is already in the deployer allowlist.
+		DeployerAlreadyAllowed,
+		/// The address is not in the deployer allowlist.
+		DeployerNotFound,
 	}
 
 	#[pallet::storage]
@@ -160,6 +205,40 @@ pub mod pallet {
 	pub(super) type AccountStorages<T: Config> =
 		StorageDoubleMap<_, Blake2_128Concat, H160, Blake2_128Concat, H256, H256, ValueQuery>;
 
+	/// The number of storage slots owned by each account.
+	#[pallet::storage]
+	#[pallet::getter(fn account_storage_count)]
+	pub(super) type AccountStorageCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, u32, ValueQuery>;
+
+	/// The last `AccountStorages` key counted by the storage count backfill.
+	///
+	/// The slots written before `AccountStorageCount` was introduced are counted in `on_idle`,
+	/// until the storage version is bumped to `STORAGE_VERSION`.
+	#[pallet::storage]
+	pub(super) type StorageCountBackfillCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// The removed accounts whose storage is waiting to be cleared in `on_idle`.
+	#[pallet::storage]
+	pub(super) type PendingStorageRemovals<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
+	/// The storage deposit of each contract created since the storage deposit was introduced.
+	#[pallet::storage]
+	#[pallet::getter(fn storage_deposit_of)]
+	pub(super) type StorageDeposits<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		H160,
+		StorageDeposit<StorageDepositBalance<T>>,
+		OptionQuery,
+	>;
+
+	/// The addresses allowed to create contracts, if `EnsureAllowedDeployer` is used.
+	#[pallet::storage]
+	pub(super) type DeployerAllowlist<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		pub accounts: BTreeMap<H160, GenesisAccount>,
@@ -173,6 +252,9 @@ pub mod pallet {
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig {
 		fn build(&self) {
+			// Nothing to backfill, the genesis slots are counted as they're written.
+			STORAGE_VERSION.put::<Pallet<T>>();
+
 			for (address, account) in &self.accounts {
 				let account_id = T::IntoAccountId::derive_substrate_address(address);
 
@@ -185,7 +267,7 @@ pub mod pallet {
 				T::RingBalanceAdapter::mutate_evm_balance(&address, account.balance);
 				Pallet::<T>::create_account(address, account.code.clone());
 				for (index, value) in &account.storage {
-					AccountStorages::<T>::insert(address, index, value);
+					Pallet::<T>::set_account_storage(address, *index, *value);
 				}
 			}
 		}
@@ -193,8 +275,28 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let version_weight = T::DbWeight::get().reads(1);
+
+			if remaining_weight < version_weight {
+				return 0;
+			}
+
+			let remaining_weight = remaining_weight - version_weight;
+
+			// The slot counters of the pending accounts aren't reliable until the backfill ends.
+			if Self::is_storage_count_backfilled() {
+				Self::clear_pending_storages(remaining_weight).saturating_add(version_weight)
+			} else {
+				Self::backfill_storage_count(remaining_weight).saturating_add(version_weight)
+			}
+		}
+	}
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Issue an EVM call operation. This is similar to a message call transaction in Ethereum.
@@ -345,8 +447,44 @@ pub mod pallet {
 				pays_fee: Pays::No,
 			})
 		}
+
+		/// Add an address to the deployer allowlist.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn add_deployer(origin: OriginFor<T>, address: H160) -> DispatchResult {
+			T::DeployerAllowlistOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				!<DeployerAllowlist<T>>::contains_key(address),
+				<Error<T>>::DeployerAlreadyAllowed
+			);
+
+			<DeployerAllowlist<T>>::insert(address, ());
+
+			Self::deposit_event(Event::DeployerAdded { address });
+
+			Ok(())
+		}
+
+		/// Remove an address from the deployer allowlist.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn remove_deployer(origin: OriginFor<T>, address: H160) -> DispatchResult {
+			T::DeployerAllowlistOrigin::ensure_origin(origin)?;
+
+			ensure!(<DeployerAllowlist<T>>::contains_key(address), <Error<T>>::DeployerNotFound);
+
+			<DeployerAllowlist<T>>::remove(address);
+
+			Self::deposit_event(Event::DeployerRemoved { address });
+
+			Ok(())
+		}
 	}
 	impl<T: Config> Pallet<T> {
+		/// Check whether the address is in the deployer allowlist.
+		pub fn is_allowed_deployer(address: &H160) -> bool {
+			<DeployerAllowlist<T>>::contains_key(address)
+		}
+
 		pub fn account_basic(address: &H160) -> Account {
 			let account_id = T::IntoAccountId::derive_substrate_address(address);
 			let nonce = <frame_system::Pallet<T>>::account_nonce(&account_id);
@@ -357,13 +495,406 @@ pub mod pallet {
 		}
 
 		pub fn remove_account(address: &H160) {
-			if AccountCodes::<T>::contains_key(address) {
+			let is_contract = AccountCodes::<T>::contains_key(address);
+
+			if is_contract {
 				let account_id = T::IntoAccountId::derive_substrate_address(address);
 				let _ = frame_system::Pallet::<T>::dec_sufficients(&account_id);
 			}
 
 			AccountCodes::<T>::remove(address);
-			AccountStorages::<T>::remove_prefix(address, None);
+
+			// The storage might be too large to be cleared in a single block, so the removal and
+			// the refund of the storage deposit are deferred to `on_idle`.
+			if is_contract || Self::has_account_storage(address) {
+				Self::queue_storage_removal(address);
+			}
+		}
+
+		/// Queue the storage of an account to be cleared in `on_idle`.
+		pub fn queue_storage_removal(address: &H160) {
+			<PendingStorageRemovals<T>>::insert(address, ());
+		}
+
+		/// Check whether the storage of an account is waiting to be cleared in `on_idle`.
+		pub fn is_storage_removal_pending(address: &H160) -> bool {
+			<PendingStorageRemovals<T>>::contains_key(address)
+		}
+
+		/// Check whether the slots written before `AccountStorageCount` was introduced have been
+		/// counted.
+		pub fn is_storage_count_backfilled() -> bool {
+			StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION
+		}
+
+		/// Get the number of storage slots owned by an account.
+		///
+		/// `None` means unknown, while the storage count is being backfilled.
+		pub fn storage_count_of(address: &H160) -> Option<u32> {
+			if Self::is_storage_count_backfilled() {
+				Some(AccountStorageCount::<T>::get(address))
+			} else {
+				None
+			}
+		}
+
+		/// Check whether an account owns any storage slot.
+		///
+		/// While the storage count is being backfilled, a missing counter doesn't mean zero, so the
+		/// slots are looked up directly.
+		fn has_account_storage(address: &H160) -> bool {
+			AccountStorageCount::<T>::contains_key(address)
+				|| (!Self::is_storage_count_backfilled()
+					&& AccountStorages::<T>::iter_key_prefix(address).next().is_some())
+		}
+
+		/// Check whether a storage slot is tracked by `AccountStorageCount`.
+		///
+		/// While the storage count is being backfilled, only the slots up to the cursor are
+		/// tracked, the rest are counted once the backfill reaches them.
+		fn is_storage_slot_counted(address: &H160, index: &H256) -> bool {
+			if Self::is_storage_count_backfilled() {
+				return true;
+			}
+
+			<StorageCountBackfillCursor<T>>::get()
+				.map(|cursor| AccountStorages::<T>::hashed_key_for(address, index) <= cursor)
+				.unwrap_or(false)
+		}
+
+		/// Set a storage slot of an account, keeping the slot counter in sync.
+		pub fn set_account_storage(address: &H160, index: H256, value: H256) {
+			let exists = AccountStorages::<T>::contains_key(address, index);
+			let counted = Self::is_storage_slot_counted(address, &index);
+
+			if value == H256::default() {
+				if exists {
+					AccountStorages::<T>::remove(address, index);
+					if counted {
+						AccountStorageCount::<T>::mutate_exists(address, |count| {
+							*count = count.and_then(|c| c.checked_sub(1)).filter(|c| *c != 0);
+						});
+					}
+					<StorageDeposits<T>>::mutate(address, |maybe_deposit| {
+						if let Some(deposit) = maybe_deposit {
+							deposit.bytes = deposit.bytes.saturating_sub(STORAGE_SLOT_SIZE);
+						}
+					});
+				}
+			} else {
+				if !exists {
+					if counted {
+						AccountStorageCount::<T>::mutate(address, |count| {
+							*count = count.saturating_add(1)
+						});
+					}
+					<StorageDeposits<T>>::mutate(address, |maybe_deposit| {
+						if let Some(deposit) = maybe_deposit {
+							deposit.bytes = deposit.bytes.saturating_add(STORAGE_SLOT_SIZE);
+						}
+					});
+				}
+
+				AccountStorages::<T>::insert(address, index, value);
+			}
+		}
+
+		/// Reset the storage of an account, before a contract is created at it.
+		///
+		/// The leftover storage might be too large to be cleared in a single block, so it's queued
+		/// to be cleared in `on_idle`, and `false` is returned. The contract must not be created
+		/// until then, otherwise it would read the stale slots and lose its own ones.
+		pub fn reset_account_storage(address: &H160) -> bool {
+			if Self::is_storage_removal_pending(address) {
+				return false;
+			}
+			if Self::has_account_storage(address) {
+				Self::queue_storage_removal(address);
+
+				return false;
+			}
+
+			true
+		}
+
+		/// Start tracking the storage deposit of a newly created contract.
+		///
+		/// The `deployer` is the origin of the creating transaction, which reserves the deposit
+		/// for the code and for the slots written by the constructor and the later executions.
+		pub fn note_contract_created(address: &H160, deployer: &H160) {
+			let code_len = AccountCodes::<T>::decode_len(address).unwrap_or(0) as u64;
+			// The account had no slot before the creation, so the constructor wrote all of them,
+			// which is bounded by the gas.
+			let slots = Self::storage_count_of(address)
+				.unwrap_or_else(|| AccountStorages::<T>::iter_key_prefix(address).count() as u32)
+				as u64;
+
+			<StorageDeposits<T>>::insert(
+				address,
+				StorageDeposit {
+					depositor: *deployer,
+					bytes: code_len.saturating_add(slots.saturating_mul(STORAGE_SLOT_SIZE)),
+					reserved: Zero::zero(),
+				},
+			);
+		}
+
+		/// Reserve or refund the storage deposit of a contract, according to its current size.
+		///
+		/// The growth is reserved from the deployer, and the shrinkage is refunded to it.
+		pub fn settle_storage_deposit(address: &H160) -> Result<(), ExitError> {
+			let mut deposit = match <StorageDeposits<T>>::get(address) {
+				Some(deposit) => deposit,
+				None => return Ok(()),
+			};
+			let required =
+				T::StorageDepositPerByte::get().saturating_mul(deposit.bytes.saturated_into());
+			let who = T::IntoAccountId::derive_substrate_address(&deposit.depositor);
+
+			if required > deposit.reserved {
+				let amount = required - deposit.reserved;
+
+				T::StorageDepositCurrency::reserve_named(&STORAGE_DEPOSIT_ID, &who, amount)
+					.map_err(|_| ExitError::OutOfFund)?;
+
+				deposit.reserved = required;
+
+				Self::deposit_event(Event::StorageDepositReserved {
+					contract: *address,
+					depositor: deposit.depositor,
+					amount,
+				});
+			} else if required < deposit.reserved {
+				let amount = deposit.reserved - required;
+
+				// Fail the execution, so that the record is reverted along with it.
+				if !T::StorageDepositCurrency::unreserve_named(&STORAGE_DEPOSIT_ID, &who, amount)
+					.is_zero()
+				{
+					return Err(ExitError::Other("Failed to refund the storage deposit".into()));
+				}
+
+				deposit.reserved = required;
+
+				Self::deposit_event(Event::StorageDepositRefunded {
+					contract: *address,
+					depositor: deposit.depositor,
+					amount,
+				});
+			} else {
+				return Ok(());
+			}
+
+			<StorageDeposits<T>>::insert(address, deposit);
+
+			Ok(())
+		}
+
+		/// Refund the storage deposit of a removed contract to its deployer.
+		///
+		/// The record is only removed once the whole deposit has been refunded, otherwise the
+		/// rest is kept to be retried, and `false` is returned.
+		fn refund_storage_deposit(address: &H160) -> bool {
+			let mut deposit = match <StorageDeposits<T>>::get(address) {
+				Some(deposit) => deposit,
+				None => return true,
+			};
+			let who = T::IntoAccountId::derive_substrate_address(&deposit.depositor);
+			let missing = T::StorageDepositCurrency::unreserve_named(
+				&STORAGE_DEPOSIT_ID,
+				&who,
+				deposit.reserved,
+			);
+			let refunded = deposit.reserved.saturating_sub(missing);
+
+			if !refunded.is_zero() {
+				Self::deposit_event(Event::StorageDepositRefunded {
+					contract: *address,
+					depositor: deposit.depositor,
+					amount: refunded,
+				});
+			}
+
+			if missing.is_zero() {
+				<StorageDeposits<T>>::remove(address);
+
+				true
+			} else {
+				log::error!(
+					target: "evm",
+					"Failed to refund {:?} of the storage deposit of {:?} to {:?}",
+					missing,
+					address,
+					deposit.depositor
+				);
+
+				deposit.reserved = missing;
+				<StorageDeposits<T>>::insert(address, deposit);
+
+				false
+			}
+		}
+
+		/// Count the slots written before `AccountStorageCount` was introduced, as much as the
+		/// weight allows.
+		///
+		/// `AccountStorages` is walked in the order of its storage keys, the cursor is kept
+		/// between the blocks, and the storage version is bumped once the walk ends.
+		pub fn backfill_storage_count(remaining_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// Reading and writing the cursor, and bumping the storage version.
+			let overhead = db_weight.reads_writes(1, 2);
+			// Finding the next key, and updating its slot counter.
+			let slot_weight = db_weight.reads_writes(2, 1);
+
+			if remaining_weight < overhead.saturating_add(slot_weight) {
+				return 0;
+			}
+
+			let prefix = AccountStorages::<T>::final_prefix();
+			// `Blake2_128Concat` puts the address after its 16 bytes hash.
+			let address_at = prefix.len() + 16;
+			let mut cursor =
+				<StorageCountBackfillCursor<T>>::get().unwrap_or_else(|| prefix.to_vec());
+			let mut consumed_weight = overhead;
+
+			loop {
+				if consumed_weight.saturating_add(slot_weight) > remaining_weight {
+					<StorageCountBackfillCursor<T>>::put(cursor);
+
+					break;
+				}
+
+				consumed_weight = consumed_weight.saturating_add(slot_weight);
+
+				match sp_io::storage::next_key(&cursor) {
+					Some(next) if next.starts_with(&prefix) => {
+						let address = H160::from_slice(&next[address_at..address_at + 20]);
+
+						AccountStorageCount::<T>::mutate(address, |count| {
+							*count = count.saturating_add(1)
+						});
+
+						cursor = next;
+					},
+					_ => {
+						<StorageCountBackfillCursor<T>>::kill();
+						STORAGE_VERSION.put::<Pallet<T>>();
+
+						log::info!(target: "evm", "Backfilled the storage count");
+
+						break;
+					},
+				}
+			}
+
+			consumed_weight
+		}
+
+		/// Clear the storage of the pending removed accounts and refund their storage deposit,
+		/// as much as the weight allows.
+		pub fn clear_pending_storages(remaining_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// Removing the slot counter, refunding and removing the deposit, and removing the
+			// queue entry.
+			let overhead = db_weight.reads_writes(3, 5);
+			let mut consumed_weight = 0;
+			// The queue is only altered after the iteration.
+			let mut addresses = <PendingStorageRemovals<T>>::iter_keys();
+			let mut cleared = Vec::new();
+
+			loop {
+				// Reserve a read for the next pending account.
+				if consumed_weight.saturating_add(db_weight.reads(1)) > remaining_weight {
+					break;
+				}
+
+				consumed_weight = consumed_weight.saturating_add(db_weight.reads(1));
+
+				let address = match addresses.next() {
+					Some(address) => address,
+					None => break,
+				};
+				let limit = remaining_weight
+					.saturating_sub(consumed_weight)
+					.saturating_sub(overhead)
+					.checked_div(db_weight.write)
+					.unwrap_or(Weight::MAX)
+					.saturated_into::<u32>();
+
+				if limit == 0 {
+					break;
+				}
+
+				match AccountStorages::<T>::remove_prefix(address, Some(limit)) {
+					KillStorageResult::AllRemoved(removed) => {
+						consumed_weight = consumed_weight
+							.saturating_add(db_weight.writes(removed as Weight))
+							.saturating_add(overhead);
+
+						AccountStorageCount::<T>::remove(address);
+					},
+					KillStorageResult::SomeRemaining(removed) => {
+						consumed_weight =
+							consumed_weight.saturating_add(db_weight.writes(removed as Weight + 1));
+
+						AccountStorageCount::<T>::mutate(address, |count| {
+							*count = count.saturating_sub(removed)
+						});
+
+						break;
+					},
+				}
+
+				// Retry the kept refund in a later block.
+				if !Self::refund_storage_deposit(&address) {
+					continue;
+				}
+
+				cleared.push(address);
+
+				Self::deposit_event(Event::StorageRemoved { address });
+			}
+
+			for address in cleared {
+				<PendingStorageRemovals<T>>::remove(address);
+			}
+
+			consumed_weight
+		}
+
+		/// Page through the storage of an account.
+		///
+		/// The slots are returned in the order of their hashed storage keys, starting after
+		/// `start_after` if it is given.
+		pub fn account_storages_paged(
+			address: &H160,
+			start_after: Option<H256>,
+			limit: u32,
+		) -> Vec<(H256, H256)> {
+			// `Blake2_128Concat` appends the 32 bytes index to its 16 bytes hash.
+			let mut prefix = AccountStorages::<T>::hashed_key_for(address, H256::default());
+			prefix.truncate(prefix.len() - 48);
+
+			let mut key = start_after
+				.map(|index| AccountStorages::<T>::hashed_key_for(address, index))
+				.unwrap_or_else(|| prefix.clone());
+			let mut slots = Vec::new();
+
+			while slots.len() < limit as usize {
+				match sp_io::storage::next_key(&key) {
+					Some(next) if next.starts_with(&prefix) => {
+						let index = H256::from_slice(&next[next.len() - 32..]);
+
+						slots.push((index, AccountStorages::<T>::get(address, index)));
+
+						key = next;
+					},
+					_ => break,
+				}
+			}
+
+			slots
 		}
 
 		/// Create an account.
@@ -411,6 +942,17 @@ pub mod pallet {
 }
 pub use pallet::*;
 
+/// The storage deposit of a contract.
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct StorageDeposit<Balance> {
+	/// The deployer of the contract, which reserves the deposit.
+	pub depositor: H160,
+	/// The bytes of the contract code and storage slots.
+	pub bytes: u64,
+	/// The currently reserved deposit.
+	pub reserved: Balance,
+}
+
 /// Handle withdrawing, refunding and depositing of transaction fees.
 /// Similar to `OnChargeTransaction` of `pallet_transaction_payment`
 pub trait OnChargeEVMTransaction<T: Config> {
@@ -431,13 +973,42 @@ pub trait OnChargeEVMTransaction<T: Config> {
 
 	/// Introduced in EIP1559 to handle the priority tip payment to the block Author.
 	fn pay_priority_fee(tip: U256);
+
+	/// Introduced in EIP1559 to handle the base fee portion of the corrected fee.
+	fn pay_base_fee(base_fee: U256);
+}
+
+/// Handle the base fee portion of the transaction fee.
+///
+/// The base fee has already been withdrawn from the sender, doing nothing here burns it.
+pub trait OnBaseFee<T: Config> {
+	fn on_base_fee(base_fee: U256);
+}
+impl<T: Config> OnBaseFee<T> for () {
+	fn on_base_fee(_: U256) {}
 }
 
-pub struct EVMCurrencyAdapter<F>(PhantomData<F>);
-impl<T, F> OnChargeEVMTransaction<T> for EVMCurrencyAdapter<F>
+/// Deposit the base fee into an account, such as the treasury.
+pub struct BaseFeeTo<A>(PhantomData<A>);
+impl<T, A> OnBaseFee<T> for BaseFeeTo<A>
+where
+	T: Config,
+	A: Get<T::AccountId>,
+{
+	fn on_base_fee(base_fee: U256) {
+		let who = A::get();
+		let account_balance = T::RingBalanceAdapter::account_balance(&who);
+		let new_account_balance = account_balance.saturating_add(base_fee);
+		T::RingBalanceAdapter::mutate_account_balance(&who, new_account_balance);
+	}
+}
+
+pub struct EVMCurrencyAdapter<F, B = ()>(PhantomData<(F, B)>);
+impl<T, F, B> OnChargeEVMTransaction<T> for EVMCurrencyAdapter<F, B>
 where
 	T: Config,
 	F: FindAuthor<T::AccountId>,
+	B: OnBaseFee<T>,
 {
 	type LiquidityInfo = U256;
 
@@ -468,6 +1039,10 @@ where
 			T::RingBalanceAdapter::mutate_account_balance(&author, new_account_balance);
 		}
 	}
+
+	fn pay_base_fee(base_fee: U256) {
+		B::on_base_fee(base_fee);
+	}
 }
 
 /// A trait to perform origin check.
@@ -490,6 +1065,29 @@ pub trait EnsureAddressOrigin<OuterOrigin> {
 	) -> Result<Self::Success, OuterOrigin>;
 }
 
+/// A trait to check whether an address could create contracts.
+pub trait EnsureCreateOrigin<T: Config> {
+	fn check_create_origin(address: &H160) -> Result<(), Error<T>>;
+}
+/// Allow everyone to create contracts.
+impl<T: Config> EnsureCreateOrigin<T> for () {
+	fn check_create_origin(_: &H160) -> Result<(), Error<T>> {
+		Ok(())
+	}
+}
+
+/// Only allow the addresses in the deployer allowlist to create contracts.
+pub struct EnsureAllowedDeployer;
+impl<T: Config> EnsureCreateOrigin<T> for EnsureAllowedDeployer {
+	fn check_create_origin(address: &H160) -> Result<(), Error<T>> {
+		if <Pallet<T>>::is_allowed_deployer(address) {
+			Ok(())
+		} else {
+			Err(<Error<T>>::CreateOriginNotAllowed)
+		}
+	}
+}
+
 /// A trait for handling currency decimal difference between native and evm tokens.
 pub trait CurrencyAdapt<T: Config> {
 	/// Get account balance, the decimal of the returned result is consistent with Ethereum.
@@ -34,7 +34,7 @@ use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData, mem, prelude
 // --- darwinia-network ---
 use crate::{
 	runner::Runner as RunnerT, AccountCodes, AccountStorages, BlockHashMapping, Config,
-	CurrencyAdapt, Error, Event, FeeCalculator, OnChargeEVMTransaction, Pallet,
+	CurrencyAdapt, EnsureCreateOrigin, Error, Event, FeeCalculator, OnChargeEVMTransaction, Pallet,
 };
 use darwinia_support::evm::DeriveSubstrateAddress;
 
@@ -103,14 +103,31 @@ impl<T: Config> Runner<T> {
 		// Deduct fee from the `source` account.
 		let fee = T::OnChargeTransaction::withdraw_fee(&source, total_fee)?;
 
+		// The price exposed to `GASPRICE`, which is the base fee plus the effective priority fee.
+		let effective_gas_price = match max_priority_fee_per_gas {
+			Some(max_priority_fee) => base_fee
+				.saturating_add(max_fee_per_gas.saturating_sub(base_fee).min(max_priority_fee)),
+			None => base_fee,
+		};
+
 		// Execute the EVM call.
-		let vicinity = Vicinity { gas_price: base_fee, origin: source };
+		let vicinity = Vicinity { gas_price: effective_gas_price, origin: source };
 
 		let metadata = StackSubstateMetadata::new(gas_limit, &config);
-		let state = SubstrateStackState::new(&vicinity, metadata);
+		let state = SubstrateStackState::new(&vicinity, metadata, is_transactional);
 		let mut executor = StackExecutor::new_with_precompiles(state, config, precompiles);
 
-		let (reason, retv) = f(&mut executor);
+		let (mut reason, retv) = f(&mut executor);
+
+		// The execution has been reverted, if the storage deposit could not be settled or a
+		// contract was created on the stale storage.
+		if let Some(e) = executor.state_mut().revert_error.take() {
+			reason = ExitReason::Error(e);
+		}
+		// Their removal was queued in the reverted execution, queue it again.
+		for address in mem::take(&mut executor.state_mut().stale_storage_accounts) {
+			<Pallet<T>>::queue_storage_removal(&address);
+		}
 
 		// Post execution.
 		let used_gas = U256::from(executor.used_gas());
@@ -129,6 +146,8 @@ impl<T: Config> Runner<T> {
 			} else {
 				(executor.fee(base_fee), None)
 			};
+		// Non-transactional calls might not withdraw anything.
+		let actual_base_fee = executor.fee(base_fee).min(total_fee);
 		log::debug!(
 			target: "evm",
 			"Execution {:?} [source: {:?}, value: {}, gas_limit: {}, actual_fee: {}, is_transactional: {}]",
@@ -159,11 +178,15 @@ impl<T: Config> Runner<T> {
 		// Actual cost (2 + 6) * 5 = 40.
 		// Refunded 200 - 40 = 160.
 		// Tip 5 * 6 = 30.
-		// Burned 200 - (160 + 30) = 10. Which is equivalent to gas_used * base_fee.
+		// Base fee 200 - (160 + 30) = 10. Which is equivalent to gas_used * base_fee.
+		//
+		// The base fee is either burned or routed elsewhere, depending on the
+		// `OnChargeTransaction` implementation.
 		T::OnChargeTransaction::correct_and_deposit_fee(&source, actual_fee, fee);
 		if let Some(actual_priority_fee) = actual_priority_fee {
 			T::OnChargeTransaction::pay_priority_fee(actual_priority_fee);
 		}
+		T::OnChargeTransaction::pay_base_fee(actual_base_fee);
 
 		let state = executor.into_state();
 
@@ -242,6 +265,8 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 		is_transactional: bool,
 		config: &evm::Config,
 	) -> Result<CreateInfo, Self::Error> {
+		T::CreateOrigin::check_create_origin(&source)?;
+
 		let precompiles = T::PrecompilesValue::get();
 		Self::execute(
 			source,
@@ -273,6 +298,8 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 		is_transactional: bool,
 		config: &evm::Config,
 	) -> Result<CreateInfo, Self::Error> {
+		T::CreateOrigin::check_create_origin(&source)?;
+
 		let precompiles = T::PrecompilesValue::get();
 		let code_hash = H256::from_slice(Keccak256::digest(&init).as_slice());
 		Self::execute(
@@ -362,6 +389,11 @@ impl<'config> SubstrateStackSubstate<'config> {
 		Ok(())
 	}
 
+	/// Whether the substate is the outermost frame of the execution.
+	pub fn is_outermost(&self) -> bool {
+		self.parent.as_ref().map(|p| p.parent.is_none()).unwrap_or(false)
+	}
+
 	pub fn deleted(&self, address: H160) -> bool {
 		if self.deletes.contains(&address) {
 			return true;
@@ -394,12 +426,24 @@ impl<'config> SubstrateStackSubstate<'config> {
 pub struct SubstrateStackState<'vicinity, 'config, T> {
 	vicinity: &'vicinity Vicinity,
 	substate: SubstrateStackSubstate<'config>,
+	/// Whether the execution is a transaction, rather than a simulation like `eth_call`.
+	is_transactional: bool,
+	/// The contracts whose storage deposit might need to be settled.
+	storage_deposit_contracts: BTreeSet<H160>,
+	/// The accounts whose leftover storage blocked a contract creation.
+	stale_storage_accounts: BTreeSet<H160>,
+	/// The error which reverted the execution at the outermost commit.
+	revert_error: Option<ExitError>,
 	_marker: PhantomData<T>,
 }
 
 impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 	/// Create a new backend with given vicinity.
-	pub fn new(vicinity: &'vicinity Vicinity, metadata: StackSubstateMetadata<'config>) -> Self {
+	pub fn new(
+		vicinity: &'vicinity Vicinity,
+		metadata: StackSubstateMetadata<'config>,
+		is_transactional: bool,
+	) -> Self {
 		Self {
 			vicinity,
 			substate: SubstrateStackSubstate {
@@ -408,9 +452,35 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 				logs: Vec::new(),
 				parent: None,
 			},
+			is_transactional,
+			storage_deposit_contracts: BTreeSet::new(),
+			stale_storage_accounts: BTreeSet::new(),
+			revert_error: None,
 			_marker: PhantomData,
 		}
 	}
+
+	/// Refuse the contracts created on the storage which is still waiting to be cleared.
+	fn ensure_no_stale_storage(&self) -> Result<(), ExitError> {
+		if self.stale_storage_accounts.is_empty() {
+			Ok(())
+		} else {
+			Err(ExitError::CreateCollision)
+		}
+	}
+
+	/// Settle the storage deposit of the touched contracts with their deployers.
+	///
+	/// The simulations are not charged, the later transactions catch up with their changes.
+	fn settle_storage_deposits(&self) -> Result<(), ExitError> {
+		if !self.is_transactional {
+			return Ok(());
+		}
+
+		self.storage_deposit_contracts
+			.iter()
+			.try_for_each(|address| <Pallet<T>>::settle_storage_deposit(address))
+	}
 }
 
 impl<'vicinity, 'config, T: Config> BackendT for SubstrateStackState<'vicinity, 'config, T> {
@@ -499,6 +569,26 @@ impl<'vicinity, 'config, T: Config> StackStateT<'config>
 	}
 
 	fn exit_commit(&mut self) -> Result<(), ExitError> {
+		// Settle the storage deposit before committing the outermost frame, so that the whole
+		// execution could be reverted if the deployer can not afford it, or if a contract was
+		// created on the stale storage.
+		if self.substate.is_outermost() {
+			if let Err(e) =
+				self.ensure_no_stale_storage().and_then(|_| self.settle_storage_deposits())
+			{
+				log::debug!(
+					target: "evm",
+					"Failed to commit the execution: {:?}",
+					e
+				);
+
+				self.substate.exit_revert()?;
+				self.revert_error = Some(e.clone());
+
+				return Err(e);
+			}
+		}
+
 		self.substate.exit_commit()
 	}
 
@@ -531,7 +621,6 @@ impl<'vicinity, 'config, T: Config> StackStateT<'config>
 				address,
 				index,
 			);
-			AccountStorages::<T>::remove(address, index);
 		} else {
 			log::debug!(
 				target: "evm",
@@ -540,12 +629,22 @@ impl<'vicinity, 'config, T: Config> StackStateT<'config>
 				index,
 				value,
 			);
-			AccountStorages::<T>::insert(address, index, value);
 		}
+
+		<Pallet<T>>::set_account_storage(&address, index, value);
+		self.storage_deposit_contracts.insert(address);
 	}
 
 	fn reset_storage(&mut self, address: H160) {
-		AccountStorages::<T>::remove_prefix(address, None);
+		if !<Pallet<T>>::reset_account_storage(&address) {
+			log::debug!(
+				target: "evm",
+				"Creating a contract on the stale storage of {:?}",
+				address
+			);
+
+			self.stale_storage_accounts.insert(address);
+		}
 	}
 
 	fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) {
@@ -564,6 +663,8 @@ impl<'vicinity, 'config, T: Config> StackStateT<'config>
 			address
 		);
 		Pallet::<T>::create_account(&address, code);
+		Pallet::<T>::note_contract_created(&address, &self.vicinity.origin);
+		self.storage_deposit_contracts.insert(address);
 	}
 
 	fn transfer(&mut self, transfer: Transfer) -> Result<(), ExitError> {
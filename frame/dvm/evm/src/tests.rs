@@ -23,16 +23,17 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 // --- paritytech ---
 use frame_support::{
-	assert_ok,
-	traits::{ConstU32, Everything, GenesisBuild},
+	assert_noop, assert_ok,
+	traits::{ConstU32, Everything, GenesisBuild, OnIdle},
+	weights::constants::RocksDbWeight,
 	ConsensusEngineId,
 };
-use frame_system::mocking::*;
+use frame_system::{mocking::*, EnsureRoot};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
-	AccountId32, RuntimeDebug,
+	AccountId32, DispatchError, RuntimeDebug,
 };
 use sp_std::prelude::*;
 // --- darwinia-network ---
@@ -58,7 +59,7 @@ impl frame_system::Config for Test {
 	type BlockNumber = u64;
 	type BlockWeights = ();
 	type Call = Call;
-	type DbWeight = ();
+	type DbWeight = RocksDbWeight;
 	type Event = Event;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
@@ -87,7 +88,7 @@ impl darwinia_balances::Config<RingInstance> for Test {
 	type Event = Event;
 	type ExistentialDeposit = ExistentialDeposit;
 	type MaxLocks = ();
-	type MaxReserves = ();
+	type MaxReserves = ConstU32<1>;
 	type ReserveIdentifier = [u8; 8];
 	type WeightInfo = ();
 }
@@ -114,12 +115,27 @@ impl pallet_timestamp::Config for Test {
 	type WeightInfo = ();
 }
 
-/// Fixed gas price of `0`.
-pub struct FixedGasPrice;
-impl FeeCalculator for FixedGasPrice {
+frame_support::parameter_types! {
+	pub static BaseFeePerGas: U256 = U256::from(1_000_000_000);
+	pub BaseFeeReceiver: AccountId32 = AccountId32::new([9; 32]);
+	pub static StorageDepositPerByte: Balance = 0;
+	pub static PermissionedCreate: bool = false;
+}
+pub struct MockBaseFee;
+impl FeeCalculator for MockBaseFee {
 	fn min_gas_price() -> U256 {
-		// Gas price is always one token per gas.
-		1_000_000_000u128.into()
+		BaseFeePerGas::get()
+	}
+}
+
+pub struct MockCreateOrigin;
+impl EnsureCreateOrigin<Test> for MockCreateOrigin {
+	fn check_create_origin(address: &H160) -> Result<(), Error<Test>> {
+		if PermissionedCreate::get() {
+			<EnsureAllowedDeployer as EnsureCreateOrigin<Test>>::check_create_origin(address)
+		} else {
+			Ok(())
+		}
 	}
 }
 
@@ -161,6 +177,10 @@ where
 		let new_account_balance = account_balance.saturating_add(tip);
 		T::RingBalanceAdapter::mutate_evm_balance(&author, new_account_balance);
 	}
+
+	fn pay_base_fee(base_fee: U256) {
+		<EVMCurrencyAdapter<()> as OnChargeEVMTransaction<T>>::pay_base_fee(base_fee)
+	}
 }
 
 /// Ensure that the origin is root.
@@ -222,8 +242,10 @@ impl Config for Test {
 	type BlockHashMapping = SubstrateBlockHashMapping<Self>;
 	type CallOrigin = EnsureAddressRoot<Self::AccountId>;
 	type ChainId = ();
+	type CreateOrigin = MockCreateOrigin;
+	type DeployerAllowlistOrigin = EnsureRoot<Self::AccountId>;
 	type Event = Event;
-	type FeeCalculator = FixedGasPrice;
+	type FeeCalculator = MockBaseFee;
 	type FindAuthor = FindAuthorTruncated;
 	type GasWeightMapping = ();
 	type IntoAccountId = ConcatConverter<Self::AccountId>;
@@ -233,6 +255,8 @@ impl Config for Test {
 	type PrecompilesValue = ();
 	type RingBalanceAdapter = MockBalanceAdapter<Self>;
 	type Runner = Runner<Self>;
+	type StorageDepositCurrency = Ring;
+	type StorageDepositPerByte = StorageDepositPerByte;
 }
 
 frame_support::construct_runtime! {
@@ -658,3 +682,702 @@ fn runner_max_fee_per_gas_gte_max_priority_fee_per_gas() {
 		assert!(res.is_err());
 	});
 }
+
+#[test]
+fn fee_should_follow_base_fee() {
+	new_test_ext().execute_with(|| {
+		BASE_FEE_PER_GAS.with(|v| *v.borrow_mut() = U256::from(1_500_000_000));
+
+		let before_call = <Test as Config>::RingBalanceAdapter::evm_balance(&H160::default());
+		let _ = EVM::call(
+			Origin::root(),
+			H160::default(),
+			H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+			Vec::new(),
+			U256::from(1),
+			1000000,
+			U256::from(2_000_000_000),
+			None,
+			None,
+			Vec::new(),
+		);
+		let total_cost = (U256::from(21_000) * U256::from(1_500_000_000)) + U256::from(1);
+		let after_call = <Test as Config>::RingBalanceAdapter::evm_balance(&H160::default());
+		assert_eq!(after_call, before_call - total_cost);
+
+		// The max fee per gas must cover the current base fee.
+		let result = EVM::call(
+			Origin::root(),
+			H160::default(),
+			H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+			Vec::new(),
+			U256::from(1),
+			1000000,
+			U256::from(1_000_000_000),
+			None,
+			None,
+			Vec::new(),
+		);
+		assert!(result.is_err());
+	});
+}
+
+#[test]
+fn gas_price_opcode_should_return_effective_gas_price() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		EVM::create_account(
+			&contract,
+			vec![
+				0x3a, // GASPRICE
+				0x60, 0x00, // PUSH1 0
+				0x52, // MSTORE
+				0x60, 0x20, // PUSH1 32
+				0x60, 0x00, // PUSH1 0
+				0xf3, // RETURN
+			],
+		);
+		let gas_price_of = |max_fee_per_gas: u64, max_priority_fee_per_gas: Option<u64>| {
+			let info = <Test as Config>::Runner::call(
+				H160::default(),
+				contract,
+				Vec::new(),
+				U256::zero(),
+				1000000,
+				Some(U256::from(max_fee_per_gas)),
+				max_priority_fee_per_gas.map(U256::from),
+				None,
+				Vec::new(),
+				true,
+				&<Test as Config>::config().clone(),
+			)
+			.unwrap();
+
+			U256::from_big_endian(&info.value)
+		};
+
+		// Base fee only.
+		assert_eq!(gas_price_of(2_000_000_000, None), U256::from(1_000_000_000));
+		// Base fee plus the full priority fee.
+		assert_eq!(gas_price_of(2_000_000_000, Some(500_000_000)), U256::from(1_500_000_000));
+		// The priority fee is capped by the max fee.
+		assert_eq!(gas_price_of(2_000_000_000, Some(1_500_000_000)), U256::from(2_000_000_000));
+	});
+}
+
+#[test]
+fn base_fee_to_should_work() {
+	new_test_ext().execute_with(|| {
+		let receiver = BaseFeeReceiver::get();
+		assert_eq!(<Test as Config>::RingBalanceAdapter::account_balance(&receiver), U256::zero());
+
+		<BaseFeeTo<BaseFeeReceiver> as OnBaseFee<Test>>::on_base_fee(U256::from(21_000));
+		assert_eq!(
+			<Test as Config>::RingBalanceAdapter::account_balance(&receiver),
+			U256::from(21_000)
+		);
+	});
+}
+
+#[test]
+fn storage_count_should_work() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		EVM::create_account(
+			&contract,
+			vec![
+				0x60, 0x00, // PUSH1 0
+				0x35, // CALLDATALOAD
+				0x60, 0x20, // PUSH1 32
+				0x35, // CALLDATALOAD
+				0x55, // SSTORE
+				0x00, // STOP
+			],
+		);
+		let sstore = |index: u64, value: u64| {
+			let mut input = H256::from_low_u64_be(value).as_bytes().to_vec();
+			input.extend_from_slice(H256::from_low_u64_be(index).as_bytes());
+
+			assert_ok!(<Test as Config>::Runner::call(
+				H160::default(),
+				contract,
+				input,
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				false,
+				&<Test as Config>::config().clone(),
+			));
+		};
+
+		sstore(0, 1);
+		sstore(1, 1);
+		assert_eq!(EVM::account_storage_count(contract), 2);
+
+		// Overwriting an existing slot.
+		sstore(1, 2);
+		assert_eq!(EVM::account_storage_count(contract), 2);
+
+		// Clearing slots.
+		sstore(0, 0);
+		assert_eq!(EVM::account_storage_count(contract), 1);
+		sstore(0, 0);
+		assert_eq!(EVM::account_storage_count(contract), 1);
+		sstore(1, 0);
+		assert_eq!(EVM::account_storage_count(contract), 0);
+		assert!(!AccountStorageCount::<Test>::contains_key(contract));
+	});
+}
+
+#[test]
+fn storage_removal_should_be_lazy() {
+	let contract = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+	let mut ext = new_test_ext();
+
+	ext.execute_with(|| {
+		EVM::create_account(&contract, vec![0x00]);
+		(1..=3).for_each(|i| {
+			EVM::set_account_storage(&contract, H256::from_low_u64_be(i), H256::repeat_byte(1))
+		});
+		assert_eq!(EVM::account_storage_count(contract), 3);
+	});
+	// The removal limit only applies to the committed storage.
+	ext.commit_all().unwrap();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		EVM::remove_account(&contract);
+		assert!(EVM::is_contract_code_empty(&contract));
+		assert!(EVM::is_storage_removal_pending(&contract));
+		assert_eq!(AccountStorages::<Test>::iter_prefix(contract).count(), 3);
+
+		// Enough weight to remove two slots.
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		let remaining_weight = db_weight.reads(1 + 1 + 3) + db_weight.writes(2 + 5);
+
+		// The slot counter is updated, the deposit and the queue are untouched.
+		assert_eq!(
+			<EVM as OnIdle<_>>::on_idle(1, remaining_weight),
+			db_weight.reads(2) + db_weight.writes(2 + 1)
+		);
+		assert_eq!(EVM::account_storage_count(contract), 1);
+		assert!(EVM::is_storage_removal_pending(&contract));
+		assert_eq!(AccountStorages::<Test>::iter_prefix(contract).count(), 1);
+
+		// Not enough weight to remove anything.
+		assert_eq!(
+			<EVM as OnIdle<_>>::on_idle(2, db_weight.reads(1 + 1 + 3) + db_weight.writes(5)),
+			db_weight.reads(2)
+		);
+		assert_eq!(EVM::account_storage_count(contract), 1);
+
+		<EVM as OnIdle<_>>::on_idle(3, remaining_weight);
+		assert_eq!(EVM::account_storage_count(contract), 0);
+		assert!(!EVM::is_storage_removal_pending(&contract));
+		assert_eq!(AccountStorages::<Test>::iter_prefix(contract).count(), 0);
+		assert!(System::events()
+			.into_iter()
+			.any(|r| r.event == Event::EVM(crate::Event::StorageRemoved { address: contract })));
+
+		// Nothing left to remove.
+		assert_eq!(<EVM as OnIdle<_>>::on_idle(4, remaining_weight), db_weight.reads(2));
+	});
+}
+
+#[test]
+fn reset_storage_should_be_lazy() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		let account = H160::from_str("1000000000000000000000000000000000000004").unwrap();
+
+		assert!(EVM::reset_account_storage(&contract));
+
+		EVM::create_account(&contract, vec![0x00]);
+		EVM::set_account_storage(&contract, H256::from_low_u64_be(1), H256::repeat_byte(1));
+		EVM::remove_account(&contract);
+		assert!(EVM::is_storage_removal_pending(&contract));
+
+		// The pending storage is left to `on_idle`.
+		assert!(!EVM::reset_account_storage(&contract));
+		assert!(EVM::is_storage_removal_pending(&contract));
+		assert_eq!(EVM::account_storage_count(contract), 1);
+		assert_eq!(AccountStorages::<Test>::iter_prefix(contract).count(), 1);
+
+		// The leftover storage of an account without code is queued.
+		EVM::set_account_storage(&account, H256::from_low_u64_be(1), H256::repeat_byte(1));
+		assert!(!EVM::is_storage_removal_pending(&account));
+		assert!(!EVM::reset_account_storage(&account));
+		assert!(EVM::is_storage_removal_pending(&account));
+
+		<EVM as OnIdle<_>>::on_idle(1, Weight::MAX);
+		assert!(EVM::reset_account_storage(&contract));
+		assert!(EVM::reset_account_storage(&account));
+	});
+}
+
+#[test]
+fn storage_count_backfill_should_be_multi_block() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		let account = H160::from_str("1000000000000000000000000000000000000004").unwrap();
+
+		// The slots written before the counter was introduced.
+		(1..=3).for_each(|i| {
+			AccountStorages::<Test>::insert(
+				contract,
+				H256::from_low_u64_be(i),
+				H256::repeat_byte(1),
+			);
+			AccountStorages::<Test>::insert(
+				account,
+				H256::from_low_u64_be(i),
+				H256::repeat_byte(1),
+			);
+		});
+		StorageVersion::new(0).put::<EVM>();
+		assert!(!EVM::is_storage_count_backfilled());
+		assert_eq!(EVM::storage_count_of(&contract), None);
+
+		// A missing counter is unknown, the slots are queued to be removed.
+		EVM::remove_account(&account);
+		assert!(EVM::is_storage_removal_pending(&account));
+		assert!(!EVM::reset_account_storage(&contract));
+		assert!(EVM::is_storage_removal_pending(&contract));
+		let _ = PendingStorageRemovals::<Test>::remove_all(None);
+
+		// Enough weight to count two slots.
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		let remaining_weight = db_weight.reads(1 + 1 + 2 * 2) + db_weight.writes(2 + 2);
+
+		assert_eq!(<EVM as OnIdle<_>>::on_idle(1, remaining_weight), remaining_weight);
+		assert_eq!(AccountStorageCount::<Test>::iter_values().sum::<u32>(), 2);
+		assert!(!EVM::is_storage_count_backfilled());
+
+		// The slots written meanwhile are counted either by the setter or by the backfill.
+		(1..=4).for_each(|i| {
+			EVM::set_account_storage(&contract, H256::from_low_u64_be(i), H256::default());
+			EVM::set_account_storage(&account, H256::from_low_u64_be(i), H256::repeat_byte(2));
+		});
+		EVM::set_account_storage(&contract, H256::from_low_u64_be(5), H256::repeat_byte(2));
+
+		while !EVM::is_storage_count_backfilled() {
+			<EVM as OnIdle<_>>::on_idle(2, remaining_weight);
+		}
+		assert_eq!(EVM::storage_count_of(&contract), Some(1));
+		assert_eq!(EVM::storage_count_of(&account), Some(4));
+		assert_eq!(StorageVersion::get::<EVM>(), STORAGE_VERSION);
+		assert!(StorageCountBackfillCursor::<Test>::get().is_none());
+
+		// Only once.
+		AccountStorages::<Test>::remove(contract, H256::from_low_u64_be(5));
+		<EVM as OnIdle<_>>::on_idle(3, remaining_weight);
+		assert_eq!(EVM::storage_count_of(&contract), Some(1));
+	});
+}
+
+#[test]
+fn account_storages_paged_should_work() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		let other = H160::from_str("1000000000000000000000000000000000000004").unwrap();
+		let slots = (1..=5)
+			.map(|i| (H256::from_low_u64_be(i), H256::from_low_u64_be(i * 10)))
+			.collect::<BTreeMap<_, _>>();
+		slots.iter().for_each(|(index, value)| {
+			EVM::set_account_storage(&contract, *index, *value);
+			EVM::set_account_storage(&other, *index, *value);
+		});
+
+		let mut paged = Vec::new();
+		let mut start_after = None;
+
+		loop {
+			let page = EVM::account_storages_paged(&contract, start_after, 2);
+
+			if page.is_empty() {
+				break;
+			}
+
+			assert!(page.len() <= 2);
+
+			start_after = page.last().map(|(index, _)| *index);
+			paged.extend(page);
+		}
+
+		assert_eq!(paged.len(), 5);
+		assert_eq!(paged.into_iter().collect::<BTreeMap<_, _>>(), slots);
+		assert!(EVM::account_storages_paged(&contract, None, 0).is_empty());
+	});
+}
+
+// Stores the first 32 bytes of the input at the slot of the second 32 bytes.
+const SSTORE_CODE: [u8; 8] = [
+	0x60, 0x00, // PUSH1 0
+	0x35, // CALLDATALOAD
+	0x60, 0x20, // PUSH1 32
+	0x35, // CALLDATALOAD
+	0x55, // SSTORE
+	0x00, // STOP
+];
+
+// Stores `1` at slot `0` and deploys the `SSTORE_CODE`.
+fn sstore_init_code() -> Vec<u8> {
+	let mut init = vec![
+		0x60, 0x01, // PUSH1 1
+		0x60, 0x00, // PUSH1 0
+		0x55, // SSTORE
+		0x67, // PUSH8
+	];
+	init.extend_from_slice(&SSTORE_CODE);
+	init.extend_from_slice(&[
+		0x60, 0x00, // PUSH1 0
+		0x52, // MSTORE
+		0x60, 0x08, // PUSH1 8
+		0x60, 0x18, // PUSH1 24
+		0xf3, // RETURN
+	]);
+
+	init
+}
+
+fn sstore(caller: H160, contract: H160, index: u64, value: u64, is_transactional: bool) {
+	let mut input = H256::from_low_u64_be(value).as_bytes().to_vec();
+	input.extend_from_slice(H256::from_low_u64_be(index).as_bytes());
+
+	let info = <Test as Config>::Runner::call(
+		caller,
+		contract,
+		input,
+		U256::zero(),
+		1000000,
+		Some(BaseFeePerGas::get()),
+		None,
+		None,
+		Vec::new(),
+		is_transactional,
+		&<Test as Config>::config().clone(),
+	)
+	.unwrap();
+	assert!(info.exit_reason.is_succeed());
+}
+
+fn create_sstore_contract(deployer: H160) -> CreateInfo {
+	<Test as Config>::Runner::create(
+		deployer,
+		sstore_init_code(),
+		U256::zero(),
+		1000000,
+		Some(BaseFeePerGas::get()),
+		None,
+		None,
+		Vec::new(),
+		true,
+		&<Test as Config>::config().clone(),
+	)
+	.unwrap()
+}
+
+fn account_id_of(address: &H160) -> AccountId32 {
+	<Test as Config>::IntoAccountId::derive_substrate_address(address)
+}
+
+fn reserved_of(who: &AccountId32) -> Balance {
+	<Ring as NamedReservableCurrency<_>>::reserved_balance_named(&STORAGE_DEPOSIT_ID, who)
+}
+
+// Charge the storage deposit only, without any transaction fee.
+fn set_storage_deposit_per_byte(deposit: Balance) {
+	BASE_FEE_PER_GAS.with(|v| *v.borrow_mut() = U256::zero());
+	STORAGE_DEPOSIT_PER_BYTE.with(|v| *v.borrow_mut() = deposit);
+}
+
+fn storage_deposit_events() -> Vec<crate::Event<Test>> {
+	System::events()
+		.into_iter()
+		.filter_map(|r| match r.event {
+			Event::EVM(
+				e @ (crate::Event::StorageDepositReserved { .. }
+				| crate::Event::StorageDepositRefunded { .. }),
+			) => Some(e),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn storage_deposit_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		set_storage_deposit_per_byte(2);
+
+		let deployer = H160::from_str("1000000000000000000000000000000000000005").unwrap();
+		let writer = H160::from_str("1000000000000000000000000000000000000006").unwrap();
+		let (deployer_id, writer_id) = (account_id_of(&deployer), account_id_of(&writer));
+		Ring::make_free_balance_be(&deployer_id, 1_000);
+		Ring::make_free_balance_be(&writer_id, 1_000);
+
+		let info = create_sstore_contract(deployer);
+		assert!(info.exit_reason.is_succeed());
+		let contract = info.value;
+		// The code and a slot.
+		let bytes = SSTORE_CODE.len() as u64 + STORAGE_SLOT_SIZE;
+		let slot_deposit = STORAGE_SLOT_SIZE * 2;
+		assert_eq!(
+			EVM::storage_deposit_of(contract),
+			Some(StorageDeposit { depositor: deployer, bytes, reserved: bytes * 2 })
+		);
+		assert_eq!(Ring::free_balance(&deployer_id), 1_000 - bytes * 2);
+		assert_eq!(reserved_of(&deployer_id), bytes * 2);
+		assert_eq!(
+			storage_deposit_events(),
+			vec![crate::Event::StorageDepositReserved {
+				contract,
+				depositor: deployer,
+				amount: bytes * 2
+			}]
+		);
+
+		// The deployer pays for the new slot, not the writer.
+		System::reset_events();
+		sstore(writer, contract, 1, 1, true);
+		assert_eq!(Ring::free_balance(&deployer_id), 1_000 - bytes * 2 - slot_deposit);
+		assert_eq!(reserved_of(&deployer_id), bytes * 2 + slot_deposit);
+		assert_eq!(Ring::free_balance(&writer_id), 1_000);
+		assert_eq!(reserved_of(&writer_id), 0);
+		assert_eq!(
+			storage_deposit_events(),
+			vec![crate::Event::StorageDepositReserved {
+				contract,
+				depositor: deployer,
+				amount: slot_deposit
+			}]
+		);
+
+		// Clearing a slot refunds the deployer.
+		System::reset_events();
+		sstore(writer, contract, 0, 0, true);
+		assert_eq!(Ring::free_balance(&deployer_id), 1_000 - bytes * 2);
+		assert_eq!(EVM::storage_deposit_of(contract).unwrap().reserved, bytes * 2);
+		assert_eq!(Ring::free_balance(&writer_id), 1_000);
+		assert_eq!(
+			storage_deposit_events(),
+			vec![crate::Event::StorageDepositRefunded {
+				contract,
+				depositor: deployer,
+				amount: slot_deposit
+			}]
+		);
+
+		// Destroy the contract, the deployer is refunded along with the storage removal.
+		System::reset_events();
+		EVM::remove_account(&contract);
+		assert_eq!(reserved_of(&deployer_id), bytes * 2);
+
+		<EVM as OnIdle<_>>::on_idle(1, Weight::MAX);
+		assert!(EVM::storage_deposit_of(contract).is_none());
+		assert_eq!(Ring::free_balance(&deployer_id), 1_000);
+		assert_eq!(reserved_of(&deployer_id), 0);
+		assert_eq!(
+			storage_deposit_events(),
+			vec![crate::Event::StorageDepositRefunded {
+				contract,
+				depositor: deployer,
+				amount: bytes * 2
+			}]
+		);
+		assert!(!EVM::is_storage_removal_pending(&contract));
+	});
+}
+
+#[test]
+fn storage_deposit_should_skip_simulated_execution() {
+	new_test_ext().execute_with(|| {
+		set_storage_deposit_per_byte(2);
+
+		let deployer = H160::from_str("1000000000000000000000000000000000000005").unwrap();
+		let caller = H160::from_str("1000000000000000000000000000000000000006").unwrap();
+		let deployer_id = account_id_of(&deployer);
+		Ring::make_free_balance_be(&deployer_id, 1_000);
+
+		let contract = create_sstore_contract(deployer).value;
+		let reserved = reserved_of(&deployer_id);
+		Ring::make_free_balance_be(&deployer_id, 0);
+
+		// Neither the unfunded caller nor the unfunded deployer is charged.
+		sstore(caller, contract, 1, 1, false);
+		assert_eq!(reserved_of(&deployer_id), reserved);
+		assert_eq!(EVM::storage_deposit_of(contract).unwrap().reserved, reserved);
+
+		// The next transaction catches up with the simulated growth.
+		Ring::make_free_balance_be(&deployer_id, 1_000);
+		sstore(caller, contract, 2, 1, true);
+		assert_eq!(reserved_of(&deployer_id), reserved + STORAGE_SLOT_SIZE * 2 * 2);
+	});
+}
+
+#[test]
+fn storage_deposit_refund_failure_should_be_kept() {
+	new_test_ext().execute_with(|| {
+		set_storage_deposit_per_byte(2);
+
+		let deployer = H160::from_str("1000000000000000000000000000000000000005").unwrap();
+		let deployer_id = account_id_of(&deployer);
+		Ring::make_free_balance_be(&deployer_id, 1_000);
+
+		let contract = create_sstore_contract(deployer).value;
+
+		// Lose a part of the reserve.
+		let _ = <Ring as NamedReservableCurrency<_>>::slash_reserved_named(
+			&STORAGE_DEPOSIT_ID,
+			&deployer_id,
+			10,
+		);
+
+		EVM::remove_account(&contract);
+		<EVM as OnIdle<_>>::on_idle(1, Weight::MAX);
+		assert_eq!(Ring::free_balance(&deployer_id), 1_000 - 10);
+		// The rest is kept to be retried, along with the queue entry.
+		assert_eq!(EVM::storage_deposit_of(contract).unwrap().reserved, 10);
+		assert!(EVM::is_storage_removal_pending(&contract));
+		assert_eq!(AccountStorages::<Test>::iter_prefix(contract).count(), 0);
+	});
+}
+
+#[test]
+fn storage_deposit_should_revert_unaffordable_execution() {
+	new_test_ext().execute_with(|| {
+		set_storage_deposit_per_byte(1_000);
+
+		let deployer = H160::from_str("1000000000000000000000000000000000000005").unwrap();
+		let deployer_id = account_id_of(&deployer);
+		Ring::make_free_balance_be(&deployer_id, 1_000);
+
+		let info = create_sstore_contract(deployer);
+		let contract = info.value;
+		assert_eq!(info.exit_reason, ExitReason::Error(ExitError::OutOfFund));
+
+		assert!(EVM::is_contract_code_empty(&contract));
+		assert!(EVM::storage_deposit_of(contract).is_none());
+		assert_eq!(EVM::account_storage_count(contract), 0);
+		assert_eq!(EVM::account_storages(contract, H256::zero()), H256::zero());
+		assert_eq!(Ring::free_balance(&deployer_id), 1_000);
+	});
+}
+
+#[test]
+fn deployer_allowlist_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let deployer = H160::from_str("1000000000000000000000000000000000000005").unwrap();
+
+		assert_noop!(
+			EVM::add_deployer(Origin::signed(AccountId32::new([1; 32])), deployer),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(EVM::add_deployer(Origin::root(), deployer));
+		assert!(EVM::is_allowed_deployer(&deployer));
+		assert_noop!(
+			EVM::add_deployer(Origin::root(), deployer),
+			<Error<Test>>::DeployerAlreadyAllowed
+		);
+
+		assert_ok!(EVM::remove_deployer(Origin::root(), deployer));
+		assert!(!EVM::is_allowed_deployer(&deployer));
+		assert_noop!(
+			EVM::remove_deployer(Origin::root(), deployer),
+			<Error<Test>>::DeployerNotFound
+		);
+
+		let events = System::events().into_iter().map(|r| r.event).collect::<Vec<_>>();
+		assert!(events.contains(&Event::EVM(crate::Event::DeployerAdded { address: deployer })));
+		assert!(events.contains(&Event::EVM(crate::Event::DeployerRemoved { address: deployer })));
+	});
+}
+
+#[test]
+fn create_should_respect_deployer_allowlist() {
+	new_test_ext().execute_with(|| {
+		PERMISSIONED_CREATE.with(|v| *v.borrow_mut() = true);
+
+		let create = |source: H160| {
+			<Test as Config>::Runner::create(
+				source,
+				sstore_init_code(),
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				false,
+				&<Test as Config>::config().clone(),
+			)
+		};
+		let create2 = |source: H160| {
+			<Test as Config>::Runner::create2(
+				source,
+				sstore_init_code(),
+				H256::default(),
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				false,
+				&<Test as Config>::config().clone(),
+			)
+		};
+		let deployer = H160::default();
+
+		assert!(matches!(create(deployer), Err(Error::CreateOriginNotAllowed)));
+		assert!(matches!(create2(deployer), Err(Error::CreateOriginNotAllowed)));
+		assert_noop!(
+			EVM::create(
+				Origin::root(),
+				deployer,
+				sstore_init_code(),
+				U256::zero(),
+				1000000,
+				U256::from(1_000_000_000),
+				None,
+				None,
+				Vec::new(),
+			),
+			<Error<Test>>::CreateOriginNotAllowed
+		);
+
+		assert_ok!(EVM::add_deployer(Origin::root(), deployer));
+		assert!(create(deployer).unwrap().exit_reason.is_succeed());
+		assert!(create2(deployer).unwrap().exit_reason.is_succeed());
+		assert_ok!(EVM::create(
+			Origin::root(),
+			deployer,
+			sstore_init_code(),
+			U256::zero(),
+			1000000,
+			U256::from(1_000_000_000),
+			None,
+			None,
+			Vec::new(),
+		));
+
+		// Calls are not restricted.
+		assert_ok!(<Test as Config>::Runner::call(
+			H160::from_str("1000000000000000000000000000000000000005").unwrap(),
+			H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+			Vec::new(),
+			U256::zero(),
+			1000000,
+			None,
+			None,
+			None,
+			Vec::new(),
+			false,
+			&<Test as Config>::config().clone(),
+		));
+	});
+}
@@ -0,0 +1,48 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition required by header-mmr RPC extensions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use darwinia_header_mmr::{MmrBatchProof, MmrProof, NodeIndex};
+
+// --- crates.io ---
+use codec::Codec;
+// --- paritytech ---
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	pub trait HeaderMmrApi<Hash>
+	where
+		Hash: Codec,
+	{
+		/// Generate the inclusion proof of the `block_number`'s leaf under the MMR of `mmr_size`.
+		fn gen_proof(block_number: NodeIndex, mmr_size: NodeIndex) -> Option<MmrProof<Hash>>;
+
+		/// Generate a single compacted inclusion proof of the `block_numbers`' leaves under the MMR
+		/// of `mmr_size`.
+		fn gen_batch_proof(
+			block_numbers: Vec<NodeIndex>,
+			mmr_size: NodeIndex,
+		) -> Option<MmrBatchProof<Hash>>;
+
+		/// The MMR node of the leaf of `parent_hash`, which is pushed on this block.
+		fn leaf_node(parent_hash: Hash) -> Option<Hash>;
+	}
+}
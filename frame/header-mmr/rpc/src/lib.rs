@@ -0,0 +1,134 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC extensions for header-mmr.
+
+pub use darwinia_header_mmr_rpc_runtime_api::{
+	HeaderMmrApi as HeaderMmrRuntimeApi, MmrBatchProof, MmrProof, NodeIndex,
+};
+
+// --- core ---
+use core::{fmt::Debug, marker::PhantomData};
+// --- std ---
+use std::sync::Arc;
+// --- crates.io ---
+use codec::Codec;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+// --- paritytech ---
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::ExecutionContext;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+const RUNTIME_ERROR: i64 = -1;
+
+#[rpc]
+pub trait HeaderMmrApi<BlockHash, Hash> {
+	/// Generate the inclusion proof of the `block_number`'s leaf under the MMR of `mmr_size`.
+	///
+	/// Requires the node to run with the off-chain indexing enabled.
+	#[rpc(name = "headerMMR_genProof")]
+	fn gen_proof(
+		&self,
+		block_number: NodeIndex,
+		mmr_size: NodeIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<MmrProof<Hash>>>;
+
+	/// Generate a single compacted inclusion proof of the `block_numbers`' leaves under the MMR of
+	/// `mmr_size`.
+	///
+	/// Requires the node to run with the off-chain indexing enabled.
+	#[rpc(name = "headerMMR_genBatchProof")]
+	fn gen_batch_proof(
+		&self,
+		block_numbers: Vec<NodeIndex>,
+		mmr_size: NodeIndex,
+		at: Option<BlockHash>,
+	) -> Result<Option<MmrBatchProof<Hash>>>;
+}
+
+pub struct HeaderMmr<Client, Block> {
+	client: Arc<Client>,
+	_marker: PhantomData<Block>,
+}
+impl<Client, Block> HeaderMmr<Client, Block> {
+	pub fn new(client: Arc<Client>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+impl<Client, Block> HeaderMmr<Client, Block>
+where
+	Block: BlockT,
+	Client: HeaderBackend<Block>,
+{
+	fn at(&self, at: Option<Block::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+impl<Client, Block, Hash> HeaderMmrApi<<Block as BlockT>::Hash, Hash> for HeaderMmr<Client, Block>
+where
+	Block: BlockT,
+	Client: 'static + Send + Sync + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	Client::Api: HeaderMmrRuntimeApi<Block, Hash>,
+	Hash: 'static + Send + Sync + Codec,
+{
+	fn gen_proof(
+		&self,
+		block_number: NodeIndex,
+		mmr_size: NodeIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<MmrProof<Hash>>> {
+		// The MMR nodes live in the off-chain storage.
+		self.client
+			.runtime_api()
+			.gen_proof_with_context(
+				&self.at(at),
+				ExecutionContext::OffchainCall(None),
+				block_number,
+				mmr_size,
+			)
+			.map_err(|e| runtime_error("Failed to generate the proof.", e))
+	}
+
+	fn gen_batch_proof(
+		&self,
+		block_numbers: Vec<NodeIndex>,
+		mmr_size: NodeIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<MmrBatchProof<Hash>>> {
+		self.client
+			.runtime_api()
+			.gen_batch_proof_with_context(
+				&self.at(at),
+				ExecutionContext::OffchainCall(None),
+				block_numbers,
+				mmr_size,
+			)
+			.map_err(|e| runtime_error("Failed to generate the batch proof.", e))
+	}
+}
+
+fn runtime_error(message: &str, e: impl Debug) -> Error {
+	Error {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: message.into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
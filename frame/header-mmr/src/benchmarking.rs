@@ -0,0 +1,55 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+// --- paritytech ---
+use frame_benchmarking::benchmarks;
+use frame_support::traits::Hooks;
+// --- darwinia-network ---
+use crate::*;
+
+/// The block number is `u32`, so there are at most 32 peaks.
+const MAX_PEAKS: u32 = 32;
+
+benchmarks! {
+	// Push a leaf into the MMR of `2^p - 1` leaves, which is the worst case of `p` peaks. The new
+	// leaf merges all of them into a single peak.
+	on_finalize {
+		let p in 0 .. MAX_PEAKS;
+
+		let leaf_count = (1_u64 << p) - 1;
+		let mmr_size = if leaf_count == 0 { 0 } else { mmr::leaf_index_to_mmr_size(leaf_count - 1) };
+
+		if mmr_size != 0 {
+			for position in mmr::helper::get_peaks(mmr_size) {
+				<Peaks<T>>::insert(position, T::Hash::default());
+			}
+		}
+
+		<MmrSize<T>>::put(mmr_size);
+	}: {
+		<Pallet<T> as Hooks<_>>::on_finalize(Default::default());
+	}
+	verify {
+		assert_eq!(<MmrSize<T>>::get(), mmr_size + p as NodeIndex + 1);
+		assert_eq!(<Peaks<T>>::iter().count(), 1);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
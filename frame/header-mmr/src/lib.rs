@@ -61,28 +61,31 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+mod benchmarking;
+
 mod primitives;
 pub use primitives::*;
 
 mod weights;
 pub use weights::WeightInfo;
 
+pub use dp_header_mmr::{MerkleMountainRangeRootLog, VersionedLeaf, LOG_PREFIX};
+
 // --- crates.io ---
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+// --- github.com ---
+use mmr::{MMRStore, MerkleProof};
 // --- paritytech ---
-use frame_support::{log, pallet_prelude::*};
+use frame_support::{log, pallet_prelude::*, traits::StorageVersion};
 use frame_system::pallet_prelude::*;
-use sp_runtime::generic::DigestItem;
-#[cfg(any(test, feature = "easy-testing"))]
-use sp_runtime::{generic::OpaqueDigestItemId, traits::Header};
-use sp_std::prelude::*;
+use sp_std::{prelude::*, vec};
 
-type NodeIndex = u64;
+pub type NodeIndex = u64;
 
-/// The prefix of [`MerkleMountainRangeRootLog`]
-pub const LOG_PREFIX: [u8; 4] = *b"MMRR";
+/// The storage version since the leaf became versioned.
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -93,6 +96,11 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		type WeightInfo: WeightInfo;
 
+		/// The leaf provider
+		///
+		/// Use `()` to keep pushing the parent hash as the leaf.
+		type LeafData: LeafDataProvider<Self::Hash>;
+
 		/// The offchain-indexing prefix
 		const INDEXING_PREFIX: &'static [u8];
 	}
@@ -107,22 +115,38 @@ pub mod pallet {
 	#[pallet::getter(fn peak_of)]
 	pub type Peaks<T: Config> = StorageMap<_, Identity, NodeIndex, T::Hash, OptionQuery>;
 
+	/// The first leaf index of each leaf version since `V1`
+	///
+	/// The leaves before the first recorded version are `V0`.
+	#[pallet::storage]
+	#[pallet::getter(fn leaf_version_since)]
+	pub type LeafVersionSince<T> = StorageMap<_, Identity, u8, NodeIndex, OptionQuery>;
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+			// The new leaf merges the peaks as many as the trailing ones of the current leaf count.
+			T::WeightInfo::on_finalize(leaf_count(<MmrSize<T>>::get()).trailing_ones())
+		}
+
 		fn on_finalize(_: BlockNumberFor<T>) {
-			let parent_hash = <frame_system::Pallet<T>>::parent_hash();
+			let leaf = T::LeafData::leaf(<frame_system::Pallet<T>>::parent_hash());
+			let version = leaf.version();
+
+			if version != 0 && !<LeafVersionSince<T>>::contains_key(version) {
+				<LeafVersionSince<T>>::insert(version, leaf_count(<MmrSize<T>>::get()));
+			}
+
 			let mut mmr = <Mmr<RuntimeStorage, T>>::new();
-			let _ = mmr.push(parent_hash);
+			let _ = mmr.push(leaf.node::<T::Hashing>());
 
 			match mmr.finalize() {
 				Ok(parent_mmr_root) => {
-					let mmr_root_log = MerkleMountainRangeRootLog::<T::Hash> {
-						prefix: LOG_PREFIX,
-						parent_mmr_root,
-					};
-					let mmr_item = DigestItem::Other(mmr_root_log.encode());
+					let mmr_item =
+						MerkleMountainRangeRootLog::<T::Hash>::new(parent_mmr_root).digest_item();
 
 					<frame_system::Pallet<T>>::deposit_log(mmr_item);
 				},
@@ -144,27 +168,177 @@ impl<T: Config> Pallet<T> {
 		<Mmr<RuntimeStorage, T>>::with_size(<MmrSize<T>>::get()).get_root().ok()
 	}
 
-	// Remove the cfg, once there's a requirement from runtime usage
-	#[cfg(any(test, feature = "easy-testing"))]
+	/// The MMR node of the leaf of `parent_hash`, the same as the one pushed in `on_finalize`.
+	pub fn leaf_node(parent_hash: T::Hash) -> T::Hash {
+		T::LeafData::leaf(parent_hash).node::<T::Hashing>()
+	}
+
+	/// The version of the leaf at `leaf_index`.
+	pub fn leaf_version_of(leaf_index: NodeIndex) -> u8 {
+		<LeafVersionSince<T>>::iter()
+			.filter(|(_, since)| *since <= leaf_index)
+			.map(|(version, _)| version)
+			.max()
+			.unwrap_or(0)
+	}
+
+	/// Generate the inclusion proof of the `block_number`'s leaf under the MMR of `mmr_size`.
+	///
+	/// The MMR nodes are read from the off-chain storage, so this must be called in the off-chain
+	/// context.
+	pub fn gen_proof(block_number: NodeIndex, mmr_size: NodeIndex) -> Option<MmrProof<T::Hash>> {
+		let MmrBatchProof { mut leaves, proof, mmr_size } =
+			Self::gen_batch_proof(vec![block_number], mmr_size)?;
+		let (_, leaf) = leaves.pop()?;
+
+		Some(MmrProof { leaf, proof, mmr_size })
+	}
+
+	/// Generate a single compacted inclusion proof of the `block_numbers`' leaves under the MMR of
+	/// `mmr_size`.
+	///
+	/// The MMR nodes are read from the off-chain storage, so this must be called in the off-chain
+	/// context.
+	pub fn gen_batch_proof(
+		mut block_numbers: Vec<NodeIndex>,
+		mmr_size: NodeIndex,
+	) -> Option<MmrBatchProof<T::Hash>> {
+		block_numbers.sort_unstable();
+		block_numbers.dedup();
+
+		if block_numbers.is_empty() || mmr_size > <MmrSize<T>>::get() {
+			return None;
+		}
+
+		let storage = <Storage<OffchainStorage, T>>::default();
+		let leaves = block_numbers
+			.iter()
+			.map(|block_number| {
+				let position = mmr::leaf_index_to_pos(*block_number);
+
+				if position >= mmr_size {
+					return None;
+				}
+
+				storage.get_elem(position).ok().flatten().map(|leaf| (*block_number, leaf))
+			})
+			.collect::<Option<Vec<_>>>()?;
+		let proof = <Mmr<OffchainStorage, T>>::with_size(mmr_size)
+			.gen_batch_proof(&block_numbers)
+			.map_err(|e| log::error!("Failed to generate the proof due to {}", e))
+			.ok()?;
+
+		Some(MmrBatchProof { leaves, proof: proof.proof_items().to_vec(), mmr_size })
+	}
+
+	/// Verify the `leaves`, pairs of the block number and the MMR node of the leaf, against the
+	/// MMR `root` of `mmr_size`.
+	pub fn verify_proof(
+		root: T::Hash,
+		leaves: Vec<(NodeIndex, T::Hash)>,
+		proof: Vec<T::Hash>,
+		mmr_size: NodeIndex,
+	) -> bool {
+		if leaves.is_empty() {
+			return false;
+		}
+
+		let leaves = leaves
+			.into_iter()
+			.map(|(block_number, leaf)| (mmr::leaf_index_to_pos(block_number), leaf))
+			.collect::<Vec<_>>();
+
+		if leaves.iter().any(|(position, _)| *position >= mmr_size) {
+			return false;
+		}
+
+		<MerkleProof<T::Hash, Hasher<T>>>::new(mmr_size, proof)
+			.verify(root, leaves)
+			.unwrap_or(false)
+	}
+
+	/// Find the parent MMR root in the header digest.
 	pub fn find_parent_mmr_root(header: &T::Header) -> Option<T::Hash> {
-		let find_parent_mmr_root = |m: MerkleMountainRangeRootLog<_>| match m.prefix {
-			LOG_PREFIX => Some(m.parent_mmr_root),
-			_ => None,
-		};
-
-		// find the first other digest with the right prefix which converts to
-		// the right kind of mmr root log.
-		header
-			.digest()
-			.convert_first(|d| d.try_to(OpaqueDigestItemId::Other).and_then(find_parent_mmr_root))
+		dp_header_mmr::find_parent_mmr_root(header)
+	}
+}
+
+/// The number of the leaves in the MMR of `mmr_size`.
+pub fn leaf_count(mmr_size: NodeIndex) -> NodeIndex {
+	if mmr_size == 0 {
+		return 0;
 	}
+
+	mmr::helper::get_peaks(mmr_size)
+		.into_iter()
+		.map(|position| 1_u64 << mmr::helper::pos_height_in_tree(position))
+		.sum()
+}
+
+#[doc(hidden)]
+pub mod migration {
+	// --- darwinia-network ---
+	use crate::*;
+
+	#[cfg(feature = "try-runtime")]
+	pub mod try_runtime {
+		// --- darwinia-network ---
+		use crate::*;
+
+		pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+			let mmr_size = <MmrSize<T>>::get();
+
+			if mmr_size == 0
+				|| mmr::helper::get_peaks(mmr_size)
+					.into_iter()
+					.all(|position| <Peaks<T>>::contains_key(position))
+			{
+				Ok(())
+			} else {
+				Err("The peaks are inconsistent with the MMR size.")
+			}
+		}
+	}
+
+	/// Migrate the MMR built from the parent hashes.
+	///
+	/// The parent hashes are exactly the `V0` leaves, so the `Peaks` and the `MmrSize` are kept as
+	/// they are. The leaves of the `Config::LeafData` will be appended to the same MMR, and
+	/// `LeafVersionSince` records where they start.
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= STORAGE_VERSION {
+			return T::DbWeight::get().reads(1);
+		}
+
+		STORAGE_VERSION.put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+}
+
+/// The inclusion proof of a leaf in the MMR.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct MmrProof<Hash> {
+	/// The MMR node of the leaf.
+	///
+	/// It's the header hash for a `V0` leaf, and the hash of the encoded `VersionedLeaf` since
+	/// `V1`.
+	pub leaf: Hash,
+	/// The proof items of the leaf.
+	pub proof: Vec<Hash>,
+	/// The size of the MMR which the proof is generated against.
+	pub mmr_size: NodeIndex,
 }
 
-#[cfg_attr(feature = "std", derive(Serialize))]
+/// The compacted inclusion proof of multiple leaves in the MMR.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
-pub struct MerkleMountainRangeRootLog<Hash> {
-	/// Specific prefix to identify the mmr root log in the digest items with Other type.
-	pub prefix: [u8; 4],
-	/// The merkle mountain range root hash.
-	pub parent_mmr_root: Hash,
+pub struct MmrBatchProof<Hash> {
+	/// The block numbers and the MMR nodes of their leaves, see `MmrProof::leaf`.
+	pub leaves: Vec<(NodeIndex, Hash)>,
+	/// The proof items of the leaves.
+	pub proof: Vec<Hash>,
+	/// The size of the MMR which the proof is generated against.
+	pub mmr_size: NodeIndex,
 }
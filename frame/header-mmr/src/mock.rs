@@ -32,7 +32,7 @@ use sp_core::{
 use sp_io::TestExternalities;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, Header as _, IdentityLookup},
 	DigestItem,
 };
 // --- darwinia-network ---
@@ -71,7 +71,23 @@ impl frame_system::Config for Test {
 	type Version = ();
 }
 
+frame_support::parameter_types! {
+	pub static WithLeafData: bool = false;
+}
+pub struct MockLeafData;
+impl LeafDataProvider<Hash> for MockLeafData {
+	type LeafData = (Hash, BlockNumber);
+
+	fn leaf(parent_hash: Hash) -> VersionedLeaf<Hash, Self::LeafData> {
+		if WithLeafData::get() {
+			VersionedLeaf::V1((parent_hash, <frame_system::Pallet<Test>>::block_number()))
+		} else {
+			VersionedLeaf::V0(parent_hash)
+		}
+	}
+}
 impl Config for Test {
+	type LeafData = MockLeafData;
 	type WeightInfo = ();
 
 	const INDEXING_PREFIX: &'static [u8] = b"header-mmr-";
@@ -2,6 +2,7 @@ pub use mmr::MMR;
 
 // --- crates.io ---
 use codec::{Decode, Encode};
+use scale_info::TypeInfo;
 // --- github.com ---
 use mmr::{Error, MMRStore, Merge, MerkleProof, Result as MMRResult};
 // --- paritytech ---
@@ -9,10 +10,26 @@ use frame_support::log;
 use sp_core::offchain::StorageKind;
 use sp_io::{offchain, offchain_index};
 use sp_runtime::traits::Hash;
-use sp_std::{marker::PhantomData, prelude::*};
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
 // --- darwinia-network ---
 use crate::*;
 
+/// Provide the leaf of the current block, similar to `pallet_mmr`'s `LeafDataProvider`.
+pub trait LeafDataProvider<Hash> {
+	/// The data which the leaf commits to, e.g. `(parent_hash, state_root, message_root)`.
+	type LeafData: Clone + Debug + PartialEq + Encode + Decode + TypeInfo;
+
+	fn leaf(parent_hash: Hash) -> VersionedLeaf<Hash, Self::LeafData>;
+}
+/// Keep pushing the parent hash, the `V0` leaf.
+impl<Hash> LeafDataProvider<Hash> for () {
+	type LeafData = ();
+
+	fn leaf(parent_hash: Hash) -> VersionedLeaf<Hash, Self::LeafData> {
+		VersionedLeaf::V0(parent_hash)
+	}
+}
+
 pub struct Hasher<T>(PhantomData<T>);
 impl<T: Config> Merge for Hasher<T> {
 	type Item = T::Hash;
@@ -152,7 +169,14 @@ where
 	T: Config,
 {
 	pub fn gen_proof(&self, index: NodeIndex) -> MMRResult<MerkleProof<T::Hash, Hasher<T>>> {
-		self.mmr.gen_proof(vec![mmr::leaf_index_to_pos(index)])
+		self.gen_batch_proof(&[index])
+	}
+
+	pub fn gen_batch_proof(
+		&self,
+		indices: &[NodeIndex],
+	) -> MMRResult<MerkleProof<T::Hash, Hasher<T>>> {
+		self.mmr.gen_proof(indices.iter().map(|index| mmr::leaf_index_to_pos(*index)).collect())
 	}
 }
 impl<T> Mmr<RuntimeStorage, T>
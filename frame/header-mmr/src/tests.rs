@@ -20,10 +20,16 @@
 
 // --- crates.io ---
 use codec::Encode;
+// --- github.com ---
+use mmr::MerkleProof;
 // --- paritytech ---
-use sp_runtime::testing::Digest;
+use frame_support::traits::{OnInitialize, StorageVersion};
+use sp_runtime::{
+	testing::Digest,
+	traits::{BlakeTwo256, Header as _},
+};
 // --- darwinia-network ---
-use crate::{mock::*, primitives::*};
+use crate::{mock::*, primitives::*, *};
 
 #[test]
 fn codec_digest_should_work() {
@@ -67,9 +73,201 @@ fn header_digest_should_work() {
 				header.digest,
 				Digest { logs: vec![header_parent_mmr_log(parent_mmr_root)] }
 			);
+			assert_eq!(HeaderMmr::find_parent_mmr_root(&header), Some(parent_mmr_root));
 
 			header = new_block();
 			parent_mmr_root = mmr::<RuntimeStorage>().get_root().unwrap();
 		}
 	});
 }
+
+#[test]
+fn gen_proof_should_work() {
+	let mut ext = new_test_ext();
+	let headers = ext.execute_with(|| run_to_block_from_genesis(10));
+
+	register_offchain_ext(&mut ext);
+	ext.execute_with(|| {
+		let mmr_size = HeaderMmr::mmr_size();
+		let root = HeaderMmr::get_root().unwrap();
+		let leaves =
+			[vec![Hash::repeat_byte(1)], headers.iter().map(|h| h.hash()).collect()].concat();
+
+		for (block_number, expected_leaf) in leaves[..10].iter().enumerate() {
+			let block_number = block_number as NodeIndex;
+			let MmrProof { leaf, proof, mmr_size: proof_mmr_size } =
+				HeaderMmr::gen_proof(block_number, mmr_size).unwrap();
+
+			assert_eq!(&leaf, expected_leaf);
+			assert_eq!(proof_mmr_size, mmr_size);
+			assert!(<MerkleProof<Hash, Hasher<Test>>>::new(mmr_size, proof)
+				.verify(root, vec![(mmr::leaf_index_to_pos(block_number), leaf)])
+				.unwrap());
+		}
+
+		// The header of block 10 is not yet in the MMR.
+		assert!(HeaderMmr::gen_proof(10, mmr_size).is_none());
+		// The MMR size is beyond the current one.
+		assert!(HeaderMmr::gen_proof(0, mmr_size + 1).is_none());
+	});
+}
+
+#[test]
+fn gen_batch_proof_should_work() {
+	let mut ext = new_test_ext();
+	let headers = ext.execute_with(|| run_to_block_from_genesis(20));
+
+	register_offchain_ext(&mut ext);
+	ext.execute_with(|| {
+		let mmr_size = HeaderMmr::mmr_size();
+		let root = HeaderMmr::get_root().unwrap();
+		let MmrBatchProof { leaves, proof, mmr_size: proof_mmr_size } =
+			HeaderMmr::gen_batch_proof(vec![19, 3, 7, 3, 0], mmr_size).unwrap();
+
+		assert_eq!(
+			leaves,
+			vec![
+				(0, Hash::repeat_byte(1)),
+				(3, headers[2].hash()),
+				(7, headers[6].hash()),
+				(19, headers[18].hash())
+			]
+		);
+		assert_eq!(proof_mmr_size, mmr_size);
+		// The compacted proof is smaller than the sum of the single proofs.
+		assert!(
+			proof.len()
+				< [0, 3, 7, 19]
+					.iter()
+					.map(|n| HeaderMmr::gen_proof(*n, mmr_size).unwrap().proof.len())
+					.sum::<usize>()
+		);
+		assert!(HeaderMmr::verify_proof(root, leaves, proof, mmr_size));
+
+		assert!(HeaderMmr::gen_batch_proof(vec![], mmr_size).is_none());
+		assert!(HeaderMmr::gen_batch_proof(vec![0, 20], mmr_size).is_none());
+	});
+}
+
+#[test]
+fn verify_proof_should_work() {
+	let mut ext = new_test_ext();
+
+	ext.execute_with(|| run_to_block_from_genesis(10));
+	register_offchain_ext(&mut ext);
+	ext.execute_with(|| {
+		let mmr_size = HeaderMmr::mmr_size();
+		let root = HeaderMmr::get_root().unwrap();
+		let MmrBatchProof { leaves, proof, .. } =
+			HeaderMmr::gen_batch_proof(vec![2, 5], mmr_size).unwrap();
+
+		assert!(HeaderMmr::verify_proof(root, leaves.clone(), proof.clone(), mmr_size));
+		// Wrong root.
+		assert!(!HeaderMmr::verify_proof(Hash::zero(), leaves.clone(), proof.clone(), mmr_size));
+		// Wrong leaf.
+		assert!(!HeaderMmr::verify_proof(
+			root,
+			vec![leaves[0], (5, Hash::zero())],
+			proof.clone(),
+			mmr_size
+		));
+		// Wrong block number.
+		assert!(!HeaderMmr::verify_proof(
+			root,
+			vec![leaves[0], (6, leaves[1].1)],
+			proof.clone(),
+			mmr_size
+		));
+		// No leaf.
+		assert!(!HeaderMmr::verify_proof(root, vec![], proof.clone(), mmr_size));
+		// Leaf beyond the MMR.
+		assert!(!HeaderMmr::verify_proof(root, vec![(10, leaves[0].1)], proof, mmr_size));
+	});
+}
+
+#[test]
+fn leaf_data_should_work() {
+	let mut ext = new_test_ext();
+	let headers = ext.execute_with(|| {
+		let mut headers = run_to_block_from_genesis(3);
+
+		assert_eq!(HeaderMmr::leaf_version_since(1), None);
+
+		WITH_LEAF_DATA.with(|v| *v.borrow_mut() = true);
+
+		for _ in 0..3 {
+			headers.push(new_block_with_parent_hash(headers[headers.len() - 1].hash()));
+		}
+
+		// The leaves of block 0 to 2 are `V0`, since block 3, the leaves are `V1`.
+		assert_eq!(HeaderMmr::leaf_version_since(1), Some(3));
+		assert_eq!(leaf_count(HeaderMmr::mmr_size()), 6);
+		assert_eq!(HeaderMmr::leaf_version_of(2), 0);
+		assert_eq!(HeaderMmr::leaf_version_of(3), 1);
+		assert_eq!(HeaderMmr::leaf_version_of(5), 1);
+
+		headers
+	});
+
+	register_offchain_ext(&mut ext);
+	ext.execute_with(|| {
+		let mmr_size = HeaderMmr::mmr_size();
+		let root = HeaderMmr::get_root().unwrap();
+		let v0_leaf = <VersionedLeaf<Hash, (Hash, BlockNumber)>>::V0(headers[1].hash());
+		let v1_leaf = <VersionedLeaf<Hash, (Hash, BlockNumber)>>::V1((headers[3].hash(), 5));
+
+		assert_eq!(v0_leaf.node::<BlakeTwo256>(), headers[1].hash());
+
+		for (block_number, leaf) in [(2, v0_leaf), (4, v1_leaf)] {
+			let MmrProof { leaf: node, proof, .. } =
+				HeaderMmr::gen_proof(block_number, mmr_size).unwrap();
+
+			assert_eq!(node, leaf.node::<BlakeTwo256>());
+			assert!(HeaderMmr::verify_proof(root, vec![(block_number, node)], proof, mmr_size));
+		}
+	});
+}
+
+#[test]
+fn migrate_should_work() {
+	new_test_ext().execute_with(|| {
+		run_to_block_from_genesis(10);
+
+		let mmr_size = HeaderMmr::mmr_size();
+		let root = HeaderMmr::get_root();
+
+		StorageVersion::new(0).put::<HeaderMmr>();
+		migration::migrate::<Test>();
+
+		assert_eq!(StorageVersion::get::<HeaderMmr>(), STORAGE_VERSION);
+		assert_eq!(HeaderMmr::mmr_size(), mmr_size);
+		assert_eq!(HeaderMmr::get_root(), root);
+	});
+}
+
+#[test]
+fn on_initialize_weight_should_work() {
+	new_test_ext().execute_with(|| {
+		// Push into an empty MMR, no merge.
+		assert_eq!(
+			<HeaderMmr as OnInitialize<BlockNumber>>::on_initialize(1),
+			<() as WeightInfo>::on_finalize(0)
+		);
+
+		// Push the 8th leaf, merges 3 peaks.
+		run_to_block_from_genesis(7);
+
+		assert_eq!(
+			<HeaderMmr as OnInitialize<BlockNumber>>::on_initialize(8),
+			<() as WeightInfo>::on_finalize(3)
+		);
+
+		// Push the 9th leaf, no merge.
+		new_block();
+
+		assert_eq!(
+			<HeaderMmr as OnInitialize<BlockNumber>>::on_initialize(9),
+			<() as WeightInfo>::on_finalize(0)
+		);
+	});
+}
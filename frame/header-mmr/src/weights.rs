@@ -1,11 +1,95 @@
-// --- paritytech ---
-use frame_support::weights::Weight;
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
 
+//! Weights for darwinia_header_mmr
+//!
+//! NOT YET GENERATED. The `on_finalize` benchmark is in `benchmarking.rs`, but these numbers are
+//! still conservative estimates. They must be replaced by the output of the command below on the
+//! reference hardware before the weights are relied on.
+
+// Regenerate with:
+// target/release/drml
+// benchmark
+// --header
+// .maintain/lincense-header
+// --execution
+// wasm
+// --heap-pages
+// 4096
+// --steps
+// 50
+// --repeat
+// 20
+// --chain
+// pangolin-dev
+// --output
+// frame/header-mmr/src/weights.rs
+// --extrinsic
+// *
+// --pallet
+// darwinia_header_mmr
+// --template
+// .maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for darwinia_header_mmr.
 pub trait WeightInfo {
-	fn on_initialize() -> Weight;
+	/// Push a leaf which merges `p` peaks.
+	fn on_finalize(p: u32, ) -> Weight;
 }
+
+/// Weights for darwinia_header_mmr using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: System ParentHash (r:1 w:0)
+	// Storage: HeaderMmr LeafVersionSince (r:1 w:0)
+	// Storage: HeaderMmr MmrSize (r:2 w:1)
+	// Storage: HeaderMmr Peaks (r:p w:p+1)
+	// Storage: System Digest (r:1 w:1)
+	fn on_finalize(p: u32, ) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
 impl WeightInfo for () {
-	fn on_initialize() -> Weight {
-		0
+	// Storage: System ParentHash (r:1 w:0)
+	// Storage: HeaderMmr LeafVersionSince (r:1 w:0)
+	// Storage: HeaderMmr MmrSize (r:2 w:1)
+	// Storage: HeaderMmr Peaks (r:p w:p+1)
+	// Storage: System Digest (r:1 w:1)
+	fn on_finalize(p: u32, ) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
 	}
 }
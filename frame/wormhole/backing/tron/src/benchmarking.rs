@@ -0,0 +1,168 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+// --- crates.io ---
+use libsecp256k1::{Message as SecpMessage, PublicKey, SecretKey};
+// --- paritytech ---
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, EnsureOrigin};
+use frame_system::RawOrigin;
+use sp_core::{H160, H256};
+use sp_io::hashing;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::prelude::*;
+// --- darwinia-network ---
+use crate::*;
+use darwinia_ecdsa_authority::primitives::{Address, AuthoritiesVerifier, Message, Signature};
+
+fn ring<T: Config>(n: u32) -> RingBalance<T> {
+	T::RingCurrency::minimum_balance().max(1_u32.into()).saturating_mul(n.into())
+}
+
+fn kton<T: Config>(n: u32) -> KtonBalance<T> {
+	T::KtonCurrency::minimum_balance().max(1_u32.into()).saturating_mul(n.into())
+}
+
+/// Fund the backing account for the redemptions.
+fn fund_backing<T: Config>() {
+	let backing = <Pallet<T>>::account_id();
+
+	T::RingCurrency::make_free_balance_be(&backing, ring::<T>(100));
+	T::KtonCurrency::make_free_balance_be(&backing, kton::<T>(100));
+}
+
+/// Replace the authorities with `s` new ones, return their signatures of the `message`.
+fn sign<T: Config>(message: &Message, s: u32) -> Vec<(Address, Signature)> {
+	let keys = (1..=s)
+		.map(|i| {
+			let mut seed = [0; 32];
+
+			seed[28..].copy_from_slice(&i.to_be_bytes());
+
+			let secret_key = SecretKey::parse(&seed).unwrap();
+			let public_key = PublicKey::from_secret_key(&secret_key).serialize();
+			let address = Address::from_slice(&hashing::keccak_256(&public_key[1..])[12..]);
+
+			(secret_key, address)
+		})
+		.collect::<Vec<_>>();
+
+	T::Authorities::set_authorities(keys.iter().map(|(_, address)| *address).collect());
+
+	keys.into_iter()
+		.map(|(secret_key, address)| {
+			let (signature, recovery_id) =
+				libsecp256k1::sign(&SecpMessage::parse(message), &secret_key);
+			let mut raw = [0; 65];
+
+			raw[..64].copy_from_slice(&signature.serialize());
+			raw[64] = recovery_id.serialize();
+
+			(address, Signature(raw))
+		})
+		.collect()
+}
+
+/// Queue a redemption of the Tron transaction `tx_id`.
+fn queue<T: Config>(tx_id: H256) {
+	<PendingRedemptions<T>>::insert(
+		tx_id,
+		PendingRedemption {
+			recipient: account("recipient", 0, 0),
+			ring_value: ring::<T>(1),
+			kton_value: kton::<T>(1),
+			queued_at: Zero::zero(),
+		},
+	);
+}
+
+benchmarks! {
+	lock {
+		let caller: T::AccountId = whitelisted_caller();
+
+		T::RingCurrency::make_free_balance_be(&caller, ring::<T>(10));
+		T::KtonCurrency::make_free_balance_be(&caller, kton::<T>(10));
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		ring::<T>(1),
+		kton::<T>(1),
+		TronAddress::from(H160::repeat_byte(1))
+	)
+	verify {
+		assert_eq!(T::RingCurrency::free_balance(&caller), ring::<T>(9));
+		assert_eq!(T::KtonCurrency::free_balance(&caller), kton::<T>(9));
+	}
+
+	// Release within the outflow limits, every signature is recovered and counted.
+	redeem {
+		let s in 1 .. T::Authorities::max_authorities();
+
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let tx_id = H256::repeat_byte(1);
+
+		fund_backing::<T>();
+
+		let signatures =
+			sign::<T>(&<Pallet<T>>::redeem_message(tx_id, &recipient, ring::<T>(1), kton::<T>(1)), s);
+	}: _(RawOrigin::Signed(caller), tx_id, recipient, ring::<T>(1), kton::<T>(1), signatures)
+	verify {
+		assert!(<RedeemedTxs<T>>::contains_key(tx_id));
+	}
+
+	approve_redemption {
+		let origin = T::ApproveOrigin::successful_origin();
+		let tx_id = H256::repeat_byte(1);
+
+		fund_backing::<T>();
+		queue::<T>(tx_id);
+	}: _<T::Origin>(origin, tx_id)
+	verify {
+		assert!(<RedeemedTxs<T>>::contains_key(tx_id));
+	}
+
+	reject_redemption {
+		let origin = T::ApproveOrigin::successful_origin();
+		let tx_id = H256::repeat_byte(1);
+
+		queue::<T>(tx_id);
+	}: _<T::Origin>(origin, tx_id)
+	verify {
+		assert!(<RejectedTxs<T>>::contains_key(tx_id));
+	}
+
+	pause {
+		let origin = T::PauseOrigin::successful_origin();
+	}: _<T::Origin>(origin)
+	verify {
+		assert!(<Paused<T>>::get());
+	}
+
+	unpause {
+		let origin = T::PauseOrigin::successful_origin();
+
+		<Paused<T>>::put(true);
+	}: _<T::Origin>(origin)
+	verify {
+		assert!(!<Paused<T>>::get());
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -17,9 +17,22 @@
 // along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
 
 //! # Tron Backing Module
+//!
+//! Lock the RING/KTON on Darwinia for the mapped tokens on Tron, and release them on the
+//! redemption which is attested by the authorities.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+
+pub mod primitives;
+pub use primitives::*;
+
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -27,44 +40,160 @@ pub use weights::WeightInfo;
 pub mod pallet {
 	pub mod types {
 		// --- darwinia-network ---
-		#[cfg(feature = "std")]
 		use crate::pallet::*;
 
 		pub type AccountId<T> = <T as frame_system::Config>::AccountId;
 		// Generic type
-		#[cfg(feature = "std")]
 		pub type RingBalance<T> = <RingCurrency<T> as Currency<AccountId<T>>>::Balance;
-		#[cfg(feature = "std")]
 		pub type KtonBalance<T> = <KtonCurrency<T> as Currency<AccountId<T>>>::Balance;
-		#[cfg(feature = "std")]
 		type RingCurrency<T> = <T as Config>::RingCurrency;
-		#[cfg(feature = "std")]
 		type KtonCurrency<T> = <T as Config>::KtonCurrency;
 	}
 	pub use types::*;
 
+	// --- crates.io ---
+	use ethabi::Token;
 	// --- paritytech ---
-	#[cfg(feature = "std")]
-	use frame_support::traits::GenesisBuild;
 	use frame_support::{
-		traits::{Currency, Get},
+		pallet_prelude::*,
+		traits::{
+			Currency,
+			ExistenceRequirement::{AllowDeath, KeepAlive},
+		},
 		PalletId,
 	};
-	use sp_runtime::traits::AccountIdConversion;
+	use frame_system::pallet_prelude::*;
+	use sp_core::{H256, U256};
+	use sp_io::hashing;
+	use sp_runtime::traits::{AccountIdConversion, SaturatedConversion, Zero};
+	use sp_std::prelude::*;
 	// --- darwinia-network ---
-	use crate::weights::WeightInfo;
+	use crate::{
+		primitives::{Outflow, PendingRedemption, TronAddress},
+		weights::WeightInfo,
+	};
+	use darwinia_ecdsa_authority::primitives::{Address, AuthoritiesVerifier, Message, Signature};
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		// --- paritytech ---
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type WeightInfo: WeightInfo;
 		// --- darwinia-network ---
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 		type RingCurrency: Currency<AccountId<Self>>;
 		type KtonCurrency: Currency<AccountId<Self>>;
+		/// The authorities which attest the redemptions.
+		type Authorities: AuthoritiesVerifier;
+		/// The maximum RING outflow in the `OutflowPeriod`.
+		#[pallet::constant]
+		type RingOutflowLimit: Get<RingBalance<Self>>;
+		/// The maximum KTON outflow in the `OutflowPeriod`.
+		#[pallet::constant]
+		type KtonOutflowLimit: Get<KtonBalance<Self>>;
+		/// The rolling window of the outflow limits, e.g. a day.
+		#[pallet::constant]
+		type OutflowPeriod: Get<Self::BlockNumber>;
+		/// The origin which could approve/reject the queued redemptions.
+		type ApproveOrigin: EnsureOrigin<Self::Origin>;
+		/// The origin which could pause/unpause the backing.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Locked the assets for the Tron recipient.
+		Locked {
+			sender: AccountId<T>,
+			recipient: TronAddress,
+			ring_value: RingBalance<T>,
+			kton_value: KtonBalance<T>,
+		},
+		/// Released the assets of the Tron redemption.
+		Redeemed {
+			tx_id: H256,
+			recipient: AccountId<T>,
+			ring_value: RingBalance<T>,
+			kton_value: KtonBalance<T>,
+		},
+		/// The redemption exceeded the outflow limits, queued for the approval.
+		RedemptionQueued {
+			tx_id: H256,
+			recipient: AccountId<T>,
+			ring_value: RingBalance<T>,
+			kton_value: KtonBalance<T>,
+		},
+		/// The queued redemption is rejected, it can't be redeemed anymore.
+		RedemptionRejected {
+			tx_id: H256,
+			recipient: AccountId<T>,
+			ring_value: RingBalance<T>,
+			kton_value: KtonBalance<T>,
+		},
+		/// The backing is paused.
+		Paused,
+		/// The backing is unpaused.
+		Unpaused,
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Nothing to lock or redeem.
+		ZeroValue,
+		/// The Tron transaction has been redeemed.
+		AlreadyRedeemed,
+		/// The signatures didn't reach the authorities' threshold.
+		BadSignatures,
+		/// The Tron transaction is waiting for the approval.
+		AlreadyQueued,
+		/// The Tron transaction has been rejected.
+		AlreadyRejected,
+		/// No such pending redemption.
+		NoPendingRedemption,
+		/// The backing is paused.
+		BackingPaused,
 	}
 
+	/// The redeemed Tron transactions and the block numbers of their redemption
+	#[pallet::storage]
+	#[pallet::getter(fn redeemed_at)]
+	pub type RedeemedTxs<T: Config> = StorageMap<_, Identity, H256, T::BlockNumber, OptionQuery>;
+
+	/// The rejected Tron transactions and the block numbers of their rejection
+	#[pallet::storage]
+	#[pallet::getter(fn rejected_at)]
+	pub type RejectedTxs<T: Config> = StorageMap<_, Identity, H256, T::BlockNumber, OptionQuery>;
+
+	/// The redemptions which exceeded the outflow limits
+	#[pallet::storage]
+	#[pallet::getter(fn pending_redemption_of)]
+	pub type PendingRedemptions<T: Config> = StorageMap<
+		_,
+		Identity,
+		H256,
+		PendingRedemption<AccountId<T>, RingBalance<T>, KtonBalance<T>, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The RING outflow in the rolling window
+	#[pallet::storage]
+	#[pallet::getter(fn ring_outflow)]
+	pub type RingOutflow<T: Config> =
+		StorageValue<_, Outflow<RingBalance<T>, T::BlockNumber>, ValueQuery>;
+
+	/// The KTON outflow in the rolling window
+	#[pallet::storage]
+	#[pallet::getter(fn kton_outflow)]
+	pub type KtonOutflow<T: Config> =
+		StorageValue<_, Outflow<KtonBalance<T>, T::BlockNumber>, ValueQuery>;
+
+	/// Whether the lock/redeem are paused
+	#[pallet::storage]
+	#[pallet::getter(fn paused)]
+	pub type Paused<T> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub backed_ring: RingBalance<T>,
@@ -91,10 +220,228 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Lock the RING/KTON into the backing account.
+		///
+		/// The relayer will issue the mapped tokens to the `recipient` on Tron.
+		#[pallet::weight(<T as Config>::WeightInfo::lock())]
+		#[frame_support::transactional]
+		pub fn lock(
+			origin: OriginFor<T>,
+			#[pallet::compact] ring_value: RingBalance<T>,
+			#[pallet::compact] kton_value: KtonBalance<T>,
+			recipient: TronAddress,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Self::ensure_not_paused()?;
+
+			ensure!(!ring_value.is_zero() || !kton_value.is_zero(), <Error<T>>::ZeroValue);
+
+			let backing = Self::account_id();
+
+			if !ring_value.is_zero() {
+				T::RingCurrency::transfer(&sender, &backing, ring_value, KeepAlive)?;
+			}
+			if !kton_value.is_zero() {
+				T::KtonCurrency::transfer(&sender, &backing, kton_value, KeepAlive)?;
+			}
+
+			Self::deposit_event(Event::Locked { sender, recipient, ring_value, kton_value });
+
+			Ok(())
+		}
+
+		/// Release the RING/KTON of the Tron transaction `tx_id` to the `recipient`.
+		///
+		/// The redemption must be signed by the authorities, see `redeem_message`.
+		/// If it exceeds any of the outflow limits, it will be queued for the approval.
+		#[pallet::weight(<T as Config>::WeightInfo::redeem(signatures.len() as _))]
+		#[frame_support::transactional]
+		pub fn redeem(
+			origin: OriginFor<T>,
+			tx_id: H256,
+			recipient: AccountId<T>,
+			#[pallet::compact] ring_value: RingBalance<T>,
+			#[pallet::compact] kton_value: KtonBalance<T>,
+			signatures: Vec<(Address, Signature)>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			Self::ensure_not_paused()?;
+
+			ensure!(!ring_value.is_zero() || !kton_value.is_zero(), <Error<T>>::ZeroValue);
+			ensure!(!<RedeemedTxs<T>>::contains_key(tx_id), <Error<T>>::AlreadyRedeemed);
+			ensure!(!<RejectedTxs<T>>::contains_key(tx_id), <Error<T>>::AlreadyRejected);
+			ensure!(!<PendingRedemptions<T>>::contains_key(tx_id), <Error<T>>::AlreadyQueued);
+			ensure!(
+				T::Authorities::verify_signatures(
+					&Self::redeem_message(tx_id, &recipient, ring_value, kton_value),
+					&signatures
+				),
+				<Error<T>>::BadSignatures
+			);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let period = T::OutflowPeriod::get();
+
+			if let (Some(ring_outflow), Some(kton_outflow)) = (
+				<RingOutflow<T>>::get().checked_add(
+					ring_value,
+					T::RingOutflowLimit::get(),
+					period,
+					now,
+				),
+				<KtonOutflow<T>>::get().checked_add(
+					kton_value,
+					T::KtonOutflowLimit::get(),
+					period,
+					now,
+				),
+			) {
+				<RingOutflow<T>>::put(ring_outflow);
+				<KtonOutflow<T>>::put(kton_outflow);
+
+				Self::release(tx_id, recipient, ring_value, kton_value)?;
+			} else {
+				<PendingRedemptions<T>>::insert(
+					tx_id,
+					PendingRedemption {
+						recipient: recipient.clone(),
+						ring_value,
+						kton_value,
+						queued_at: now,
+					},
+				);
+
+				Self::deposit_event(Event::RedemptionQueued {
+					tx_id,
+					recipient,
+					ring_value,
+					kton_value,
+				});
+			}
+
+			Ok(())
+		}
+
+		/// Release the queued redemption regardless of the outflow limits.
+		#[pallet::weight(<T as Config>::WeightInfo::approve_redemption())]
+		#[frame_support::transactional]
+		pub fn approve_redemption(origin: OriginFor<T>, tx_id: H256) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			Self::ensure_not_paused()?;
+
+			let PendingRedemption { recipient, ring_value, kton_value, .. } =
+				<PendingRedemptions<T>>::take(tx_id).ok_or(<Error<T>>::NoPendingRedemption)?;
+
+			Self::release(tx_id, recipient, ring_value, kton_value)
+		}
+
+		/// Drop the queued redemption, e.g. a suspicious one.
+		///
+		/// The Tron transaction can't be redeemed anymore.
+		#[pallet::weight(<T as Config>::WeightInfo::reject_redemption())]
+		pub fn reject_redemption(origin: OriginFor<T>, tx_id: H256) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			let PendingRedemption { recipient, ring_value, kton_value, .. } =
+				<PendingRedemptions<T>>::take(tx_id).ok_or(<Error<T>>::NoPendingRedemption)?;
+
+			<RejectedTxs<T>>::insert(tx_id, <frame_system::Pallet<T>>::block_number());
+
+			Self::deposit_event(Event::RedemptionRejected {
+				tx_id,
+				recipient,
+				ring_value,
+				kton_value,
+			});
+
+			Ok(())
+		}
+
+		/// Stop the lock, redeem and approval.
+		#[pallet::weight(<T as Config>::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			<Paused<T>>::put(true);
+
+			Self::deposit_event(Event::Paused);
+
+			Ok(())
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::unpause())]
+		pub fn unpause(origin: OriginFor<T>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			<Paused<T>>::kill();
+
+			Self::deposit_event(Event::Unpaused);
+
+			Ok(())
+		}
+	}
 	impl<T: Config> Pallet<T> {
 		pub fn account_id() -> T::AccountId {
 			T::PalletId::get().into_account()
 		}
+
+		fn ensure_not_paused() -> DispatchResult {
+			ensure!(!<Paused<T>>::get(), <Error<T>>::BackingPaused);
+
+			Ok(())
+		}
+
+		fn release(
+			tx_id: H256,
+			recipient: AccountId<T>,
+			ring_value: RingBalance<T>,
+			kton_value: KtonBalance<T>,
+		) -> DispatchResult {
+			let backing = Self::account_id();
+
+			if !ring_value.is_zero() {
+				// Keep the backing account alive, it was funded with the `minimum_balance` at
+				// genesis.
+				T::RingCurrency::transfer(&backing, &recipient, ring_value, KeepAlive)?;
+			}
+			if !kton_value.is_zero() {
+				T::KtonCurrency::transfer(&backing, &recipient, kton_value, AllowDeath)?;
+			}
+
+			<RedeemedTxs<T>>::insert(tx_id, <frame_system::Pallet<T>>::block_number());
+
+			Self::deposit_event(Event::Redeemed { tx_id, recipient, ring_value, kton_value });
+
+			Ok(())
+		}
+
+		/// The message which the authorities sign for a redemption.
+		///
+		/// `Redeem(bytes32 tx_id,bytes recipient,uint256 ring_value,uint256 kton_value)`
+		pub fn redeem_message(
+			tx_id: H256,
+			recipient: &AccountId<T>,
+			ring_value: RingBalance<T>,
+			kton_value: KtonBalance<T>,
+		) -> Message {
+			T::Authorities::signable_message(&ethabi::encode(&[
+				Token::FixedBytes(
+					hashing::keccak_256(
+						b"Redeem(bytes32 tx_id,bytes recipient,uint256 ring_value,uint256 kton_value)",
+					)
+					.into(),
+				),
+				Token::FixedBytes(tx_id.as_ref().into()),
+				Token::Bytes(recipient.encode()),
+				Token::Uint(U256::from(ring_value.saturated_into::<u128>())),
+				Token::Uint(U256::from(kton_value.saturated_into::<u128>())),
+			]))
+		}
 	}
 }
 pub use pallet::*;
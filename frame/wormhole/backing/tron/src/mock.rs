@@ -0,0 +1,260 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+// --- core ---
+use core::iter;
+// --- crates.io ---
+use libsecp256k1::{PublicKey, SecretKey};
+// --- paritytech ---
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{ConstU32, Everything, GenesisBuild},
+	PalletId,
+};
+use frame_system::{mocking::*, EnsureRoot};
+use sp_core::H256;
+use sp_io::{crypto, hashing, TestExternalities};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+// --- darwinia-network ---
+use crate::{self as to_tron_backing, *};
+use darwinia_ecdsa_authority::primitives::{Address, AuthoritiesVerifier, Message, Signature};
+
+pub type Block = MockBlock<Test>;
+pub type UncheckedExtrinsic = MockUncheckedExtrinsic<Test>;
+pub type AccountId = u64;
+pub type Balance = u128;
+
+darwinia_support::impl_test_account_data! {}
+
+impl frame_system::Config for Test {
+	type AccountData = AccountData<Balance>;
+	type AccountId = AccountId;
+	type BaseCallFilter = Everything;
+	type BlockHashCount = ();
+	type BlockLength = ();
+	type BlockNumber = u64;
+	type BlockWeights = ();
+	type Call = Call;
+	type DbWeight = ();
+	type Event = Event;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type Header = Header;
+	type Index = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type MaxConsumers = ConstU32<16>;
+	type OnKilledAccount = ();
+	type OnNewAccount = ();
+	type OnSetCode = ();
+	type Origin = Origin;
+	type PalletInfo = PalletInfo;
+	type SS58Prefix = ();
+	type SystemWeightInfo = ();
+	type Version = ();
+}
+
+frame_support::parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+impl darwinia_balances::Config<RingInstance> for Test {
+	type AccountStore = System;
+	type Balance = Balance;
+	type BalanceInfo = AccountData<Balance>;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+impl darwinia_balances::Config<KtonInstance> for Test {
+	type AccountStore = System;
+	type Balance = Balance;
+	type BalanceInfo = AccountData<Balance>;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+frame_support::parameter_types! {
+	pub static ReplacedAuthorities: Option<Vec<Address>> = None;
+}
+/// Require 2/3 of the authorities, 2 of the 3 by default.
+pub struct MockAuthorities;
+impl AuthoritiesVerifier for MockAuthorities {
+	fn signable_message(data: &[u8]) -> Message {
+		hashing::keccak_256(data)
+	}
+
+	fn verify_signatures(message: &Message, signatures: &[(Address, Signature)]) -> bool {
+		let authorities = authorities();
+		let mut signers = Vec::new();
+
+		for (address, signature) in signatures {
+			if let Ok(public_key) = crypto::secp256k1_ecdsa_recover(signature.as_ref(), message) {
+				if authorities.contains(address)
+					&& !signers.contains(address)
+					&& hashing::keccak_256(&public_key)[12..] == address[..]
+				{
+					signers.push(*address);
+				}
+			}
+		}
+
+		!signers.is_empty() && signers.len() * 3 >= authorities.len() * 2
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_authorities() -> u32 {
+		3
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_authorities(authorities: Vec<Address>) {
+		REPLACED_AUTHORITIES.with(|v| *v.borrow_mut() = Some(authorities));
+	}
+}
+
+frame_support::parameter_types! {
+	pub const TronBackingPalletId: PalletId = PalletId(*b"da/trobk");
+	pub const RingOutflowLimit: Balance = 500;
+	pub const KtonOutflowLimit: Balance = 50;
+	pub const OutflowPeriod: u64 = 10;
+}
+impl Config for Test {
+	type ApproveOrigin = EnsureRoot<AccountId>;
+	type Authorities = MockAuthorities;
+	type Event = Event;
+	type KtonCurrency = Kton;
+	type KtonOutflowLimit = KtonOutflowLimit;
+	type OutflowPeriod = OutflowPeriod;
+	type PalletId = TronBackingPalletId;
+	type PauseOrigin = EnsureRoot<AccountId>;
+	type RingCurrency = Ring;
+	type RingOutflowLimit = RingOutflowLimit;
+	type WeightInfo = ();
+}
+
+frame_support::construct_runtime! {
+	pub enum Test
+	where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Ring: darwinia_balances::<Instance1>::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Kton: darwinia_balances::<Instance2>::{Pallet, Call, Storage, Config<T>, Event<T>},
+		TronBacking: to_tron_backing::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+}
+
+pub fn new_test_ext() -> TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	darwinia_balances::GenesisConfig::<Test, RingInstance> { balances: vec![(1, 1_000)] }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	darwinia_balances::GenesisConfig::<Test, KtonInstance> { balances: vec![(1, 1_000)] }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	<to_tron_backing::GenesisConfig<Test> as GenesisBuild<Test>>::assimilate_storage(
+		&to_tron_backing::GenesisConfig { backed_ring: 1_000, backed_kton: 1_000 },
+		&mut storage,
+	)
+	.unwrap();
+
+	let mut ext = TestExternalities::from(storage);
+
+	ext.execute_with(|| System::set_block_number(1));
+
+	ext
+}
+
+pub fn gen_pair(byte: u8) -> (SecretKey, Address) {
+	let seed = iter::repeat(byte).take(32).collect::<Vec<_>>();
+	let secret_key = SecretKey::parse_slice(&seed).unwrap();
+	let public_key = PublicKey::from_secret_key(&secret_key).serialize();
+	let address = Address::from_slice(&hashing::keccak_256(&public_key[1..65])[12..]);
+
+	(secret_key, address)
+}
+
+pub fn authorities() -> Vec<Address> {
+	ReplacedAuthorities::get().unwrap_or_else(|| (1..=3).map(|byte| gen_pair(byte).1).collect())
+}
+
+pub fn sign(secret_key: &SecretKey, message: &Message) -> Signature {
+	let (sig, recovery_id) = libsecp256k1::sign(&libsecp256k1::Message::parse(message), secret_key);
+	let mut signature = [0u8; 65];
+
+	signature[0..64].copy_from_slice(&sig.serialize()[..]);
+	signature[64] = recovery_id.serialize();
+
+	Signature(signature)
+}
+
+/// Sign the message with the authorities generated from the `bytes`.
+pub fn sign_with(bytes: &[u8], message: &Message) -> Vec<(Address, Signature)> {
+	bytes
+		.iter()
+		.map(|byte| {
+			let (secret_key, address) = gen_pair(*byte);
+
+			(address, sign(&secret_key, message))
+		})
+		.collect()
+}
+
+pub fn redeem(
+	tx_id: H256,
+	recipient: AccountId,
+	ring_value: Balance,
+	kton_value: Balance,
+) -> DispatchResult {
+	TronBacking::redeem(
+		Origin::signed(1),
+		tx_id,
+		recipient,
+		ring_value,
+		kton_value,
+		sign_with(&[1, 2], &TronBacking::redeem_message(tx_id, &recipient, ring_value, kton_value)),
+	)
+}
+
+pub fn tron_backing_events() -> Vec<crate::Event<Test>> {
+	let events = System::events()
+		.into_iter()
+		.filter_map(|e| match e.event {
+			Event::TronBacking(e) => Some(e),
+			_ => None,
+		})
+		.collect();
+
+	System::reset_events();
+
+	events
+}
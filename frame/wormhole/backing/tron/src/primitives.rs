@@ -0,0 +1,300 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+// --- core ---
+use core::fmt::{self, Formatter};
+#[cfg(feature = "std")]
+use core::str::FromStr;
+// --- crates.io ---
+use codec::{Decode, Encode, Input};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+// --- paritytech ---
+use sp_core::H160;
+#[cfg(feature = "std")]
+use sp_io::hashing;
+use sp_runtime::{traits::AtLeast32BitUnsigned, Perbill, RuntimeDebug};
+
+/// The Tron address.
+///
+/// The `0x41` prefix followed by the 20 bytes of the Ethereum style address, which is displayed in
+/// base58check, e.g. `TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, TypeInfo)]
+pub struct TronAddress([u8; 21]);
+impl TronAddress {
+	/// The prefix of the Tron mainnet address.
+	pub const PREFIX: u8 = 0x41;
+
+	pub fn from_slice(bytes: &[u8]) -> Result<Self, TronAddressError> {
+		if bytes.len() != 21 {
+			return Err(TronAddressError::InvalidLength);
+		}
+		if bytes[0] != Self::PREFIX {
+			return Err(TronAddressError::InvalidPrefix);
+		}
+
+		let mut address = [0; 21];
+
+		address.copy_from_slice(bytes);
+
+		Ok(Self(address))
+	}
+
+	pub fn as_bytes(&self) -> &[u8; 21] {
+		&self.0
+	}
+
+	/// Parse the base58check string, validate its checksum and prefix.
+	#[cfg(feature = "std")]
+	pub fn from_base58check(s: &str) -> Result<Self, TronAddressError> {
+		let bytes = bs58::decode(s).into_vec().map_err(|_| TronAddressError::InvalidBase58)?;
+
+		if bytes.len() != 25 {
+			return Err(TronAddressError::InvalidLength);
+		}
+
+		let (payload, checksum) = bytes.split_at(21);
+
+		if Self::checksum(payload) != checksum {
+			return Err(TronAddressError::InvalidChecksum);
+		}
+
+		Self::from_slice(payload)
+	}
+
+	#[cfg(feature = "std")]
+	pub fn to_base58check(&self) -> String {
+		bs58::encode([self.0.as_ref(), &Self::checksum(&self.0)].concat()).into_string()
+	}
+
+	#[cfg(feature = "std")]
+	fn checksum(payload: &[u8]) -> [u8; 4] {
+		let mut checksum = [0; 4];
+
+		checksum.copy_from_slice(&hashing::sha2_256(&hashing::sha2_256(payload))[..4]);
+
+		checksum
+	}
+}
+impl Decode for TronAddress {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+		let bytes = <[u8; 21]>::decode(input)?;
+
+		Self::from_slice(&bytes).map_err(|_| "Invalid Tron address prefix".into())
+	}
+}
+impl From<H160> for TronAddress {
+	fn from(address: H160) -> Self {
+		let mut bytes = [Self::PREFIX; 21];
+
+		bytes[1..].copy_from_slice(address.as_bytes());
+
+		Self(bytes)
+	}
+}
+impl From<TronAddress> for H160 {
+	fn from(address: TronAddress) -> Self {
+		H160::from_slice(&address.0[1..])
+	}
+}
+impl fmt::Debug for TronAddress {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{}", self.to_base58check())
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{:?}", self.0)
+	}
+}
+#[cfg(feature = "std")]
+impl fmt::Display for TronAddress {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{}", self.to_base58check())
+	}
+}
+#[cfg(feature = "std")]
+impl FromStr for TronAddress {
+	type Err = TronAddressError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::from_base58check(s)
+	}
+}
+#[cfg(feature = "std")]
+impl Serialize for TronAddress {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&self.to_base58check())
+	}
+}
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for TronAddress {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let s = String::deserialize(deserializer)?;
+
+		Self::from_base58check(&s)
+			.map_err(|e| D::Error::custom(format!("Invalid Tron address {}, {:?}", s, e)))
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TronAddressError {
+	InvalidBase58,
+	InvalidLength,
+	InvalidPrefix,
+	InvalidChecksum,
+}
+
+/// The outflow of an asset from the backing account.
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct Outflow<Balance, BlockNumber> {
+	pub amount: Balance,
+	pub updated_at: BlockNumber,
+}
+impl<Balance, BlockNumber> Outflow<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	/// Add the `value` into the outflow at `now`.
+	///
+	/// The window is rolling, the `limit` is released linearly in every `period`. Return `None`
+	/// if the outflow will exceed the `limit`.
+	pub fn checked_add(
+		&self,
+		value: Balance,
+		limit: Balance,
+		period: BlockNumber,
+		now: BlockNumber,
+	) -> Option<Self> {
+		let elapsed = now.saturating_sub(self.updated_at);
+		let released = if elapsed >= period {
+			self.amount
+		} else {
+			Perbill::from_rational(elapsed, period).mul_floor(limit)
+		};
+		let amount = self.amount.saturating_sub(released).checked_add(&value)?;
+
+		if amount > limit {
+			None
+		} else {
+			Some(Self { amount, updated_at: now })
+		}
+	}
+}
+
+/// The redemption which exceeded the outflow limit, waiting for the approval.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct PendingRedemption<AccountId, RingBalance, KtonBalance, BlockNumber> {
+	pub recipient: AccountId,
+	pub ring_value: RingBalance,
+	pub kton_value: KtonBalance,
+	pub queued_at: BlockNumber,
+}
+
+#[cfg(test)]
+mod tests {
+	// --- darwinia-network ---
+	use super::*;
+
+	const USDT: &str = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
+	const USDT_HEX: &str = "0x41a614f803b6fd780986a42c78ec9c7f77e6ded13c";
+
+	#[test]
+	fn base58check_should_work() {
+		let address = TronAddress::from_str(USDT).unwrap();
+
+		assert_eq!(array_bytes::bytes2hex("0x", address.as_bytes()), USDT_HEX);
+		assert_eq!(address.to_string(), USDT);
+		assert_eq!(
+			TronAddress::from(H160::zero()).to_string(),
+			"T9yD14Nj9j7xAB4dbGeiX9h8unkKHxuWwb"
+		);
+
+		// Bad checksum.
+		assert_eq!(
+			TronAddress::from_str("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6u"),
+			Err(TronAddressError::InvalidChecksum)
+		);
+		// Bitcoin address, valid checksum with the `0x00` prefix.
+		assert_eq!(
+			TronAddress::from_str("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2"),
+			Err(TronAddressError::InvalidPrefix)
+		);
+		assert_eq!(TronAddress::from_str("T9yD14Nj9j7x"), Err(TronAddressError::InvalidLength));
+		assert_eq!(TronAddress::from_str("0x41"), Err(TronAddressError::InvalidBase58));
+	}
+
+	#[test]
+	fn h160_conversion_should_work() {
+		let h160 = H160::from_slice(&array_bytes::hex2bytes_unchecked(&USDT_HEX[4..]));
+		let address = TronAddress::from(h160);
+
+		assert_eq!(address, TronAddress::from_str(USDT).unwrap());
+		assert_eq!(H160::from(address), h160);
+	}
+
+	#[test]
+	fn outflow_should_work() {
+		let outflow = <Outflow<u64, u64>>::default();
+		let outflow = outflow.checked_add(60, 100, 10, 0).unwrap();
+
+		assert_eq!(outflow, Outflow { amount: 60, updated_at: 0 });
+		// Exceed the limit.
+		assert!(outflow.checked_add(41, 100, 10, 0).is_none());
+		// 5 blocks later, 50 released.
+		assert_eq!(
+			outflow.checked_add(90, 100, 10, 5),
+			Some(Outflow { amount: 100, updated_at: 5 })
+		);
+		assert!(outflow.checked_add(91, 100, 10, 5).is_none());
+		// A period later, all released.
+		assert_eq!(
+			outflow.checked_add(100, 100, 10, 10),
+			Some(Outflow { amount: 100, updated_at: 10 })
+		);
+	}
+
+	#[test]
+	fn codec_should_work() {
+		let address = TronAddress::from_str(USDT).unwrap();
+		let encoded = address.encode();
+
+		assert_eq!(encoded, address.as_bytes().to_vec());
+		assert_eq!(TronAddress::decode(&mut &*encoded).unwrap(), address);
+
+		let mut encoded = encoded;
+
+		encoded[0] = 0;
+
+		assert!(TronAddress::decode(&mut &*encoded).is_err());
+		assert_eq!(serde_json::to_string(&address).unwrap(), format!("\"{}\"", USDT));
+		assert_eq!(serde_json::from_str::<TronAddress>(&format!("\"{}\"", USDT)).unwrap(), address);
+		assert!(
+			serde_json::from_str::<TronAddress>("\"TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6u\"").is_err()
+		);
+	}
+}
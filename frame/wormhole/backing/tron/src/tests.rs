@@ -0,0 +1,279 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+// --- paritytech ---
+use frame_support::{assert_noop, assert_ok};
+use sp_core::{H160, H256};
+use sp_runtime::DispatchError;
+// --- darwinia-network ---
+use crate::{mock::*, Event, *};
+
+#[test]
+fn lock_should_work() {
+	new_test_ext().execute_with(|| {
+		let backing = TronBacking::account_id();
+		let backed_ring = Ring::free_balance(&backing);
+		let backed_kton = Kton::free_balance(&backing);
+		let recipient = TronAddress::from(H160::repeat_byte(1));
+
+		assert_ok!(TronBacking::lock(Origin::signed(1), 100, 10, recipient));
+		assert_eq!(Ring::free_balance(&1), 900);
+		assert_eq!(Kton::free_balance(&1), 990);
+		assert_eq!(Ring::free_balance(&backing), backed_ring + 100);
+		assert_eq!(Kton::free_balance(&backing), backed_kton + 10);
+		assert_eq!(
+			tron_backing_events(),
+			vec![Event::Locked { sender: 1, recipient, ring_value: 100, kton_value: 10 }]
+		);
+
+		assert_noop!(
+			TronBacking::lock(Origin::signed(1), 0, 0, recipient),
+			<Error<Test>>::ZeroValue
+		);
+		assert_noop!(
+			TronBacking::lock(Origin::signed(1), 1_001, 0, recipient),
+			RingError::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn redeem_should_work() {
+	new_test_ext().execute_with(|| {
+		let backing = TronBacking::account_id();
+		let backed_ring = Ring::free_balance(&backing);
+		let backed_kton = Kton::free_balance(&backing);
+		let tx_id = H256::repeat_byte(1);
+		let message = TronBacking::redeem_message(tx_id, &2, 100, 10);
+
+		assert_ok!(TronBacking::redeem(
+			Origin::signed(1),
+			tx_id,
+			2,
+			100,
+			10,
+			sign_with(&[1, 2], &message)
+		));
+		assert_eq!(Ring::free_balance(&2), 100);
+		assert_eq!(Kton::free_balance(&2), 10);
+		assert_eq!(Ring::free_balance(&backing), backed_ring - 100);
+		assert_eq!(Kton::free_balance(&backing), backed_kton - 10);
+		assert_eq!(TronBacking::redeemed_at(tx_id), Some(1));
+		assert_eq!(
+			tron_backing_events(),
+			vec![Event::Redeemed { tx_id, recipient: 2, ring_value: 100, kton_value: 10 }]
+		);
+
+		// Replay.
+		assert_noop!(
+			TronBacking::redeem(Origin::signed(1), tx_id, 2, 100, 10, sign_with(&[1, 2], &message)),
+			<Error<Test>>::AlreadyRedeemed
+		);
+	});
+}
+
+#[test]
+fn redeem_with_bad_signatures_should_fail() {
+	new_test_ext().execute_with(|| {
+		let tx_id = H256::repeat_byte(1);
+		let message = TronBacking::redeem_message(tx_id, &2, 100, 10);
+
+		// Below the threshold.
+		assert_noop!(
+			TronBacking::redeem(Origin::signed(1), tx_id, 2, 100, 10, sign_with(&[1], &message)),
+			<Error<Test>>::BadSignatures
+		);
+		// Duplicated signer.
+		assert_noop!(
+			TronBacking::redeem(Origin::signed(1), tx_id, 2, 100, 10, sign_with(&[1, 1], &message)),
+			<Error<Test>>::BadSignatures
+		);
+		// Not an authority.
+		assert_noop!(
+			TronBacking::redeem(Origin::signed(1), tx_id, 2, 100, 10, sign_with(&[1, 4], &message)),
+			<Error<Test>>::BadSignatures
+		);
+		// Tampered amount.
+		assert_noop!(
+			TronBacking::redeem(
+				Origin::signed(1),
+				tx_id,
+				2,
+				1_000,
+				10,
+				sign_with(&[1, 2], &message)
+			),
+			<Error<Test>>::BadSignatures
+		);
+		assert_noop!(
+			TronBacking::redeem(Origin::signed(1), tx_id, 2, 0, 0, sign_with(&[1, 2], &message)),
+			<Error<Test>>::ZeroValue
+		);
+	});
+}
+
+#[test]
+fn redeem_exceeding_outflow_limits_should_be_queued() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(redeem(H256::repeat_byte(1), 2, 400, 0));
+		assert_eq!(Ring::free_balance(&2), 400);
+		assert_eq!(TronBacking::ring_outflow(), Outflow { amount: 400, updated_at: 1 });
+
+		// 400 + 200 > 500.
+		let tx_id = H256::repeat_byte(2);
+
+		tron_backing_events();
+
+		assert_ok!(redeem(tx_id, 2, 200, 0));
+		assert_eq!(Ring::free_balance(&2), 400);
+		assert_eq!(TronBacking::redeemed_at(tx_id), None);
+		assert_eq!(
+			TronBacking::pending_redemption_of(tx_id),
+			Some(PendingRedemption { recipient: 2, ring_value: 200, kton_value: 0, queued_at: 1 })
+		);
+		assert_eq!(
+			tron_backing_events(),
+			vec![Event::RedemptionQueued { tx_id, recipient: 2, ring_value: 200, kton_value: 0 }]
+		);
+		assert_noop!(redeem(tx_id, 2, 200, 0), <Error<Test>>::AlreadyQueued);
+
+		// The KTON limit is independent.
+		assert_ok!(redeem(H256::repeat_byte(3), 2, 0, 50));
+		assert_eq!(Kton::free_balance(&2), 50);
+
+		// Half of the limit is released after half of the period.
+		System::set_block_number(6);
+
+		assert_ok!(redeem(H256::repeat_byte(4), 2, 300, 0));
+		assert_eq!(Ring::free_balance(&2), 700);
+		assert_eq!(TronBacking::ring_outflow(), Outflow { amount: 450, updated_at: 6 });
+
+		assert_ok!(redeem(H256::repeat_byte(5), 2, 51, 0));
+		assert!(TronBacking::pending_redemption_of(H256::repeat_byte(5)).is_some());
+	});
+}
+
+#[test]
+fn approve_redemption_should_work() {
+	new_test_ext().execute_with(|| {
+		let tx_id = H256::repeat_byte(1);
+
+		assert_ok!(redeem(tx_id, 2, 600, 60));
+		assert_eq!(Ring::free_balance(&2), 0);
+
+		tron_backing_events();
+
+		assert_noop!(
+			TronBacking::approve_redemption(Origin::signed(1), tx_id),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TronBacking::approve_redemption(Origin::root(), tx_id));
+		assert_eq!(Ring::free_balance(&2), 600);
+		assert_eq!(Kton::free_balance(&2), 60);
+		assert_eq!(TronBacking::pending_redemption_of(tx_id), None);
+		assert_eq!(TronBacking::redeemed_at(tx_id), Some(1));
+		// The approved redemption doesn't consume the outflow limits.
+		assert_eq!(TronBacking::ring_outflow(), Default::default());
+		assert_eq!(
+			tron_backing_events(),
+			vec![Event::Redeemed { tx_id, recipient: 2, ring_value: 600, kton_value: 60 }]
+		);
+
+		assert_noop!(
+			TronBacking::approve_redemption(Origin::root(), tx_id),
+			<Error<Test>>::NoPendingRedemption
+		);
+		assert_noop!(redeem(tx_id, 2, 600, 60), <Error<Test>>::AlreadyRedeemed);
+	});
+}
+
+#[test]
+fn reject_redemption_should_work() {
+	new_test_ext().execute_with(|| {
+		let tx_id = H256::repeat_byte(1);
+
+		assert_ok!(redeem(tx_id, 2, 600, 60));
+
+		tron_backing_events();
+
+		assert_noop!(
+			TronBacking::reject_redemption(Origin::signed(1), tx_id),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TronBacking::reject_redemption(Origin::root(), tx_id));
+		assert_eq!(Ring::free_balance(&2), 0);
+		assert_eq!(Kton::free_balance(&2), 0);
+		assert_eq!(TronBacking::pending_redemption_of(tx_id), None);
+		assert_eq!(TronBacking::redeemed_at(tx_id), None);
+		assert_eq!(TronBacking::rejected_at(tx_id), Some(1));
+		assert_eq!(
+			tron_backing_events(),
+			vec![Event::RedemptionRejected {
+				tx_id,
+				recipient: 2,
+				ring_value: 600,
+				kton_value: 60
+			}]
+		);
+
+		assert_noop!(
+			TronBacking::reject_redemption(Origin::root(), tx_id),
+			<Error<Test>>::NoPendingRedemption
+		);
+		assert_noop!(
+			TronBacking::approve_redemption(Origin::root(), tx_id),
+			<Error<Test>>::NoPendingRedemption
+		);
+		// The rejected transaction can't be queued again.
+		assert_noop!(redeem(tx_id, 2, 600, 60), <Error<Test>>::AlreadyRejected);
+	});
+}
+
+#[test]
+fn pause_should_work() {
+	new_test_ext().execute_with(|| {
+		let recipient = TronAddress::from(H160::repeat_byte(1));
+		let tx_id = H256::repeat_byte(1);
+
+		assert_ok!(redeem(tx_id, 2, 600, 0));
+		assert_noop!(TronBacking::pause(Origin::signed(1)), DispatchError::BadOrigin);
+
+		tron_backing_events();
+
+		assert_ok!(TronBacking::pause(Origin::root()));
+		assert!(TronBacking::paused());
+		assert_eq!(tron_backing_events(), vec![Event::Paused]);
+		assert_noop!(
+			TronBacking::lock(Origin::signed(1), 100, 0, recipient),
+			<Error<Test>>::BackingPaused
+		);
+		assert_noop!(redeem(H256::repeat_byte(2), 2, 100, 0), <Error<Test>>::BackingPaused);
+		assert_noop!(
+			TronBacking::approve_redemption(Origin::root(), tx_id),
+			<Error<Test>>::BackingPaused
+		);
+
+		assert_noop!(TronBacking::unpause(Origin::signed(1)), DispatchError::BadOrigin);
+		assert_ok!(TronBacking::unpause(Origin::root()));
+		assert!(!TronBacking::paused());
+		assert_eq!(tron_backing_events(), vec![Event::Unpaused]);
+		assert_ok!(TronBacking::lock(Origin::signed(1), 100, 0, recipient));
+		assert_ok!(redeem(H256::repeat_byte(2), 2, 100, 0));
+		assert_ok!(TronBacking::approve_redemption(Origin::root(), tx_id));
+	});
+}
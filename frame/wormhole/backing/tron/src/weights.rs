@@ -1,2 +1,167 @@
-pub trait WeightInfo {}
-impl WeightInfo for () {}
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for to_tron_backing
+//!
+//! NOT YET GENERATED. The benchmarks are in `benchmarking.rs`, but these numbers are still
+//! conservative estimates. They must be replaced by the output of the command below on the
+//! reference hardware before the weights are relied on.
+
+// Regenerate with:
+// target/release/drml
+// benchmark
+// --header
+// .maintain/lincense-header
+// --execution
+// wasm
+// --heap-pages
+// 4096
+// --steps
+// 50
+// --repeat
+// 20
+// --chain
+// pangolin-dev
+// --output
+// frame/wormhole/backing/tron/src/weights.rs
+// --extrinsic
+// *
+// --pallet
+// to_tron_backing
+// --template
+// .maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for to_tron_backing.
+pub trait WeightInfo {
+	fn lock() -> Weight;
+	fn redeem(s: u32, ) -> Weight;
+	fn approve_redemption() -> Weight;
+	fn reject_redemption() -> Weight;
+	fn pause() -> Weight;
+	fn unpause() -> Weight;
+}
+
+/// Weights for to_tron_backing using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: TronBacking Paused (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn lock() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TronBacking Paused (r:1 w:0)
+	// Storage: TronBacking RedeemedTxs (r:1 w:1)
+	// Storage: TronBacking RejectedTxs (r:1 w:0)
+	// Storage: TronBacking PendingRedemptions (r:1 w:0)
+	// Storage: EcdsaAuthority Authorities (r:1 w:0)
+	// Storage: TronBacking RingOutflow (r:1 w:1)
+	// Storage: TronBacking KtonOutflow (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn redeem(s: u32, ) -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add((50_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TronBacking Paused (r:1 w:0)
+	// Storage: TronBacking PendingRedemptions (r:1 w:1)
+	// Storage: TronBacking RedeemedTxs (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn approve_redemption() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: TronBacking PendingRedemptions (r:1 w:1)
+	// Storage: TronBacking RejectedTxs (r:0 w:1)
+	fn reject_redemption() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TronBacking Paused (r:0 w:1)
+	fn pause() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TronBacking Paused (r:0 w:1)
+	fn unpause() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: TronBacking Paused (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn lock() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TronBacking Paused (r:1 w:0)
+	// Storage: TronBacking RedeemedTxs (r:1 w:1)
+	// Storage: TronBacking RejectedTxs (r:1 w:0)
+	// Storage: TronBacking PendingRedemptions (r:1 w:0)
+	// Storage: EcdsaAuthority Authorities (r:1 w:0)
+	// Storage: TronBacking RingOutflow (r:1 w:1)
+	// Storage: TronBacking KtonOutflow (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn redeem(s: u32, ) -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add((50_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: TronBacking Paused (r:1 w:0)
+	// Storage: TronBacking PendingRedemptions (r:1 w:1)
+	// Storage: TronBacking RedeemedTxs (r:0 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn approve_redemption() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: TronBacking PendingRedemptions (r:1 w:1)
+	// Storage: TronBacking RejectedTxs (r:0 w:1)
+	fn reject_redemption() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TronBacking Paused (r:0 w:1)
+	fn pause() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TronBacking Paused (r:0 w:1)
+	fn unpause() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
@@ -27,6 +27,7 @@ use sc_cli::*;
 #[cfg(feature = "try-runtime")]
 use try_runtime_cli::TryRuntimeCmd;
 // --- darwinia-network ---
+use drml_node_service::header_mmr::HeaderMmrConfig;
 use drml_rpc::EthRpcConfig;
 
 /// An overarching CLI command definition.
@@ -72,6 +73,10 @@ pub struct Run {
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
 	pub dvm_args: DvmArgs,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub header_mmr_args: HeaderMmrArgs,
 }
 
 /// Possible subcommands of the main binary.
@@ -179,6 +184,29 @@ impl DvmArgs {
 	}
 }
 
+#[derive(Debug, StructOpt)]
+pub struct HeaderMmrArgs {
+	/// Rebuild the offchain header MMR nodes from the chain history.
+	///
+	/// Useful for the node which was synced without `--enable-offchain-indexing`. Pangolin only.
+	#[structopt(long)]
+	pub header_mmr_rebuild: bool,
+
+	/// Prune the offchain header MMR nodes of the leaves which are deeper than this number of
+	/// blocks from the finalized head. The peaks required by the newer proofs are always kept.
+	/// Pangolin only.
+	#[structopt(long)]
+	pub header_mmr_pruning_depth: Option<u32>,
+}
+impl HeaderMmrArgs {
+	pub fn build_header_mmr_config(&self) -> HeaderMmrConfig {
+		HeaderMmrConfig {
+			rebuild: self.header_mmr_rebuild,
+			pruning_depth: self.header_mmr_pruning_depth,
+		}
+	}
+}
+
 #[cfg(feature = "template")]
 arg_enum! {
 	/// Available Sealing methods.
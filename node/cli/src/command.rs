@@ -174,11 +174,22 @@ pub fn run() -> CliResult<()> {
 			let authority_discovery_disabled = cli.run.authority_discovery_disabled;
 
 			if chain_spec.is_pangolin() {
+				let header_mmr_config = cli.run.header_mmr_args.build_header_mmr_config();
+
 				runner.run_node_until_exit(|config| async move {
-					pangolin_service::new_full(config, authority_discovery_disabled, eth_rpc_config)
-						.map(|(task_manager, _, _)| task_manager)
-						.map_err(CliError::from)
+					pangolin_service::new_full(
+						config,
+						authority_discovery_disabled,
+						eth_rpc_config,
+						header_mmr_config,
+					)
+					.map(|(task_manager, _, _)| task_manager)
+					.map_err(CliError::from)
 				})
+			} else if cli.run.header_mmr_args.build_header_mmr_config().is_enabled() {
+				Err("Pangoro doesn't maintain the header MMR, the `--header-mmr-*` options are only \
+					supported by Pangolin."
+					.into())
 			} else {
 				runner.run_node_until_exit(|config| async move {
 					pangoro_service::new_full(config, authority_discovery_disabled, eth_rpc_config)
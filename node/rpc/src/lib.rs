@@ -153,7 +153,9 @@ where
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
 		+ fp_rpc::EthereumRuntimeRPCApi<Block>
 		+ fp_rpc::ConvertTransactionRuntimeApi<Block>
-		+ moonbeam_rpc_primitives_debug::DebugRuntimeApi<Block>,
+		+ moonbeam_rpc_primitives_debug::DebugRuntimeApi<Block>
+		+ darwinia_ecdsa_authority_rpc::EcdsaAuthorityRuntimeApi<Block>
+		+ darwinia_header_mmr_rpc::HeaderMmrRuntimeApi<Block, Hash>,
 	P: 'static + Sync + Send + sc_transaction_pool_api::TransactionPool<Block = Block>,
 	SC: 'static + sp_consensus::SelectChain<Block>,
 	B: 'static + Send + Sync + sc_client_api::Backend<Block>,
@@ -173,6 +175,8 @@ where
 	use sc_sync_state_rpc::*;
 	use substrate_frame_rpc_system::*;
 	// --- darwinia-network ---
+	use darwinia_ecdsa_authority_rpc::*;
+	use darwinia_header_mmr_rpc::*;
 	use moonbeam_rpc_debug::*;
 	use moonbeam_rpc_trace::*;
 
@@ -290,6 +294,8 @@ where
 		true,
 	)));
 	io.extend_with(Web3ApiServer::to_delegate(Web3Api::new(client.clone())));
+	io.extend_with(EcdsaAuthorityApi::to_delegate(EcdsaAuthority::new(client.clone())));
+	io.extend_with(HeaderMmrApi::to_delegate(HeaderMmr::new(client.clone())));
 
 	if ethapi_debug_targets.iter().any(|cmd| matches!(cmd.as_str(), "debug" | "trace")) {
 		if let Some(trace_requester) = rpc_requesters.trace {
@@ -123,7 +123,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: sp_runtime::create_runtime_str!("Pangolin"),
 	impl_name: sp_runtime::create_runtime_str!("Pangolin"),
 	authoring_version: 0,
-	spec_version: 3_00_00_0,
+	spec_version: 3_01_00_0,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 0,
@@ -168,8 +168,8 @@ frame_support::construct_runtime! {
 		Session: pallet_session::{Pallet, Call, Storage, Config<T>, Event} = 12,
 		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event, ValidateUnsigned} = 13,
 		Beefy: pallet_beefy::{Pallet, Storage, Config<T>} = 55,
-		MessageGadget: darwinia_message_gadget::{Pallet, Call, Storage, Config} = 58,
-		EcdsaAuthority: darwinia_ecdsa_authority::{Pallet, Call, Storage, Config, Event<T>} = 66,
+		MessageGadget: darwinia_message_gadget::{Pallet, Call, Storage, Config, Event<T>} = 58,
+		EcdsaAuthority: darwinia_ecdsa_authority::{Pallet, Call, Storage, Config, Event<T>, ValidateUnsigned} = 66,
 		ImOnline: pallet_im_online::{Pallet, Call, Storage, Config<T>, Event<T>, ValidateUnsigned} = 14,
 		AuthorityDiscovery: pallet_authority_discovery::{Pallet, Config} = 15,
 		HeaderMmr: darwinia_header_mmr::{Pallet, Storage} = 16,
@@ -204,7 +204,7 @@ frame_support::construct_runtime! {
 
 		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 32,
 
-		TronBacking: to_tron_backing::{Pallet, Config<T>} = 39,
+		TronBacking: to_tron_backing::{Pallet, Call, Storage, Config<T>, Event<T>} = 39,
 
 		EVM: darwinia_evm::{Pallet, Call, Storage, Config, Event<T>} = 40,
 		Ethereum: darwinia_ethereum::{Pallet, Call, Storage, Config, Event<T>, Origin} = 41,
@@ -490,6 +490,49 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl darwinia_ecdsa_authority_rpc_runtime_api::EcdsaAuthorityApi<Block> for Runtime {
+		fn pending_authorities_change(
+		) -> Option<darwinia_ecdsa_authority_rpc_runtime_api::SigningProgress> {
+			EcdsaAuthority::pending_authorities_change()
+		}
+
+		fn pending_new_message_root(
+			feed: darwinia_ecdsa_authority_rpc_runtime_api::Feed,
+		) -> Option<darwinia_ecdsa_authority_rpc_runtime_api::SigningProgress> {
+			EcdsaAuthority::pending_new_message_root(feed)
+		}
+	}
+
+	impl darwinia_evm_rpc_runtime_api::EvmStorageApi<Block> for Runtime {
+		fn storage_count(address: H160) -> Option<u32> {
+			darwinia_evm::Pallet::<Runtime>::storage_count_of(&address)
+		}
+
+		fn storage_paged(address: H160, start_after: Option<H256>, limit: u32) -> Vec<(H256, H256)> {
+			darwinia_evm::Pallet::<Runtime>::account_storages_paged(&address, start_after, limit)
+		}
+	}
+
+	impl darwinia_header_mmr_rpc_runtime_api::HeaderMmrApi<Block, Hash> for Runtime {
+		fn gen_proof(
+			block_number: darwinia_header_mmr_rpc_runtime_api::NodeIndex,
+			mmr_size: darwinia_header_mmr_rpc_runtime_api::NodeIndex,
+		) -> Option<darwinia_header_mmr_rpc_runtime_api::MmrProof<Hash>> {
+			HeaderMmr::gen_proof(block_number, mmr_size)
+		}
+
+		fn gen_batch_proof(
+			block_numbers: Vec<darwinia_header_mmr_rpc_runtime_api::NodeIndex>,
+			mmr_size: darwinia_header_mmr_rpc_runtime_api::NodeIndex,
+		) -> Option<darwinia_header_mmr_rpc_runtime_api::MmrBatchProof<Hash>> {
+			HeaderMmr::gen_batch_proof(block_numbers, mmr_size)
+		}
+
+		fn leaf_node(parent_hash: Hash) -> Option<Hash> {
+			Some(HeaderMmr::leaf_node(parent_hash))
+		}
+	}
+
 	impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 		fn chain_id() -> u64 {
 			<Runtime as darwinia_evm::Config>::ChainId::get()
@@ -759,7 +802,7 @@ sp_api::impl_runtime_apis! {
 			// list_benchmark!(list, extra, pallet_session, SessionBench::<Runtime>);
 			list_benchmark!(list, extra, pallet_grandpa, Grandpa);
 			list_benchmark!(list, extra, pallet_im_online, ImOnline);
-			// list_benchmark!(list, extra, darwinia_header_mmr, HeaderMMR);
+			list_benchmark!(list, extra, darwinia_header_mmr, HeaderMmr);
 			list_benchmark!(list, extra, pallet_democracy, Democracy);
 			list_benchmark!(list, extra, pallet_collective, Council);
 			list_benchmark!(list, extra, pallet_collective, TechnicalCommittee);
@@ -779,7 +822,7 @@ sp_api::impl_runtime_apis! {
 			// list_benchmark!(list, extra, to_ethereum_backing, EthereumBacking);
 			// list_benchmark!(list, extra, darwinia_relayer_game, EthereumRelayerGame);
 			// list_benchmark!(list, extra, darwinia_relay_authority, EcdsaRelayAuthority);
-			// list_benchmark!(list, extra, to_tron_backing, TronBacking);
+			list_benchmark!(list, extra, to_tron_backing, TronBacking);
 			list_benchmark!(list, extra, pallet_bridge_grandpa, BridgePangoroGrandpa);
 			list_benchmark!(list, extra, pallet_bridge_grandpa, BridgeRococoGrandpa);
 			// TODO: https://github.com/darwinia-network/darwinia-parachain/issues/66
@@ -834,7 +877,7 @@ sp_api::impl_runtime_apis! {
 			// TODO https://github.com/darwinia-network/darwinia-common/issues/1356
 			// add_benchmark!(params, batches, pallet_grandpa, Grandpa);
 			add_benchmark!(params, batches, pallet_im_online, ImOnline);
-			// add_benchmark!(params, batches, darwinia_header_mmr, HeaderMMR);
+			add_benchmark!(params, batches, darwinia_header_mmr, HeaderMmr);
 			add_benchmark!(params, batches, pallet_democracy, Democracy);
 			add_benchmark!(params, batches, pallet_collective, Council);
 			add_benchmark!(params, batches, pallet_collective, TechnicalCommittee);
@@ -854,7 +897,7 @@ sp_api::impl_runtime_apis! {
 			// add_benchmark!(params, batches, to_ethereum_backing, EthereumBacking);
 			// add_benchmark!(params, batches, darwinia_relayer_game, EthereumRelayerGame);
 			// add_benchmark!(list, extra, darwinia_relay_authority, EcdsaRelayAuthority);
-			// add_benchmark!(params, batches, to_tron_backing, TronBacking);
+			add_benchmark!(params, batches, to_tron_backing, TronBacking);
 			add_benchmark!(params, batches, pallet_bridge_grandpa, BridgePangoroGrandpa);
 			add_benchmark!(params, batches, pallet_bridge_grandpa, BridgeRococoGrandpa);
 			// TODO: https://github.com/darwinia-network/darwinia-parachain/issues/66
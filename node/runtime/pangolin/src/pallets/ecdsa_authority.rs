@@ -1,25 +1,43 @@
 // --- paritytech ---
-use sp_runtime::Perbill;
+use sp_runtime::{transaction_validity::TransactionPriority, Perbill};
 // --- darwinia-network ---
 use crate::*;
-use darwinia_ecdsa_authority::Config;
-use darwinia_message_gadget::MessageRootGetter;
+use darwinia_ecdsa_authority::{
+	primitives::{RootSource, SingleFeed},
+	Config,
+};
+use darwinia_message_gadget::{MessageRootGetter, MessageRootWeight};
 
 frame_support::parameter_types! {
 	pub const ChainId: &'static [u8] = b"43";
 	pub const MaxPendingPeriod: BlockNumber = 100;
+	pub const MaxAuthoritiesChangePendingPeriod: BlockNumber = DAYS;
+	pub const MaxSignatureRoundRecords: u32 = 100;
 	pub const SignThreshold: Perbill = Perbill::from_percent(60);
 	pub const SyncInterval: BlockNumber = 10;
+	pub const TargetChainId: u64 = 5;
+	pub const MessageRootSource: RootSource = [0; 4];
+	pub const EcdsaAuthorityUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
 }
 static_assertions::const_assert!(SyncInterval::get() < MaxPendingPeriod::get());
+static_assertions::const_assert!(SyncInterval::get() < MaxAuthoritiesChangePendingPeriod::get());
 
 impl Config for Runtime {
 	type ChainId = ChainId;
 	type Event = Event;
 	type MaxAuthorities = ConstU32<3>;
+	type MaxAuthoritiesChangePendingPeriod = MaxAuthoritiesChangePendingPeriod;
 	type MaxPendingPeriod = MaxPendingPeriod;
-	type MessageRoot = MessageRootGetter<Self>;
+	type MaxSignatureRoundRecords = MaxSignatureRoundRecords;
+	type MessageRoots = SingleFeed<
+		TargetChainId,
+		MessageRootSource,
+		MessageRootGetter<Self>,
+		MessageRootWeight<Self>,
+	>;
+	type OnSignatureRound = ();
 	type SignThreshold = SignThreshold;
 	type SyncInterval = SyncInterval;
+	type UnsignedPriority = EcdsaAuthorityUnsignedPriority;
 	type WeightInfo = ();
 }
@@ -11,6 +11,7 @@ use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_simple::{ECRecover, Identity, Ripemd160, Sha256};
 use pallet_session::FindAccountFromAuthorIndex;
 use sp_core::{crypto::ByteArray, H160, U256};
+use sp_runtime::traits::AccountIdConversion;
 // --- darwinia-network ---
 use crate::*;
 use darwinia_ethereum::{
@@ -18,9 +19,12 @@ use darwinia_ethereum::{
 	EthereumBlockHashMapping,
 };
 use darwinia_evm::{
-	runner::stack::Runner, Config, EVMCurrencyAdapter, EnsureAddressTruncated, GasWeightMapping,
+	runner::stack::Runner, BaseFeeTo, Config, EVMCurrencyAdapter, EnsureAddressTruncated,
+	GasWeightMapping,
 };
 use darwinia_evm_precompile_dispatch::Dispatch;
+use darwinia_evm_precompile_ecdsa_authority::EcdsaAuthority;
+use darwinia_evm_precompile_header_mmr::HeaderMmr;
 use darwinia_evm_precompile_kton::{Erc20Metadata, KtonERC20};
 use darwinia_evm_precompile_state_storage::{StateStorage, StorageFilterT};
 use darwinia_support::evm::ConcatConverter;
@@ -56,7 +60,7 @@ where
 		Self(Default::default())
 	}
 
-	pub fn used_addresses() -> [H160; 12] {
+	pub fn used_addresses() -> [H160; 14] {
 		[
 			addr(1),
 			addr(2),
@@ -70,6 +74,8 @@ where
 			addr(1024),
 			addr(1025),
 			addr(1026),
+			addr(2049),
+			addr(2050),
 		]
 	}
 }
@@ -92,6 +98,8 @@ impl Erc20Metadata for KtonERC20MetaData {
 impl<R> PrecompileSet for PangolinPrecompiles<R>
 where
 	Dispatch<R>: Precompile,
+	EcdsaAuthority<R>: Precompile,
+	HeaderMmr<R>: Precompile,
 	KtonERC20<R, KtonERC20MetaData>: Precompile,
 	R: darwinia_ethereum::Config,
 	StateStorage<R, StorageFilter>: Precompile,
@@ -133,6 +141,11 @@ where
 			a if a == addr(1026) => Some(<KtonERC20<R, KtonERC20MetaData>>::execute(
 				input, target_gas, context, is_static,
 			)),
+			// Darwinia precompiles: 2048+ for experimental precompiles.
+			a if a == addr(2049) =>
+				Some(<EcdsaAuthority<R>>::execute(input, target_gas, context, is_static)),
+			a if a == addr(2050) =>
+				Some(<HeaderMmr<R>>::execute(input, target_gas, context, is_static)),
 			_ => None,
 		}
 	}
@@ -142,13 +155,6 @@ where
 	}
 }
 
-pub struct FixedGasPrice;
-impl FeeCalculator for FixedGasPrice {
-	fn min_gas_price() -> U256 {
-		U256::from(GWEI)
-	}
-}
-
 pub struct FixedGasWeightMapping;
 impl GasWeightMapping for FixedGasWeightMapping {
 	fn gas_to_weight(gas: u64) -> Weight {
@@ -168,6 +174,8 @@ frame_support::parameter_types! {
 	pub const ChainId: u64 = 43;
 	pub BlockGasLimit: U256 = U256::from(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT / WEIGHT_PER_GAS);
 	pub PrecompilesValue: PangolinPrecompiles<Runtime> = PangolinPrecompiles::<_>::new();
+	pub const StorageDepositPerByte: Balance = 100 * MICRO;
+	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account();
 }
 
 impl Config for Runtime {
@@ -175,15 +183,20 @@ impl Config for Runtime {
 	type BlockHashMapping = EthereumBlockHashMapping<Self>;
 	type CallOrigin = EnsureAddressTruncated<Self::AccountId>;
 	type ChainId = ChainId;
+	type CreateOrigin = ();
+	type DeployerAllowlistOrigin = RootOrMoreThanHalf<CouncilCollective>;
 	type Event = Event;
-	type FeeCalculator = FixedGasPrice;
+	type FeeCalculator = BaseFee;
 	type FindAuthor = EthereumFindAuthor<Babe>;
 	type GasWeightMapping = FixedGasWeightMapping;
 	type IntoAccountId = ConcatConverter<Self::AccountId>;
 	type KtonBalanceAdapter = CurrencyAdapter<Self, Kton, KtonRemainBalance>;
-	type OnChargeTransaction = EVMCurrencyAdapter<FindAccountFromAuthorIndex<Self, Babe>>;
+	type OnChargeTransaction =
+		EVMCurrencyAdapter<FindAccountFromAuthorIndex<Self, Babe>, BaseFeeTo<TreasuryAccount>>;
 	type PrecompilesType = PangolinPrecompiles<Self>;
 	type PrecompilesValue = PrecompilesValue;
 	type RingBalanceAdapter = CurrencyAdapter<Self, Ring, RingRemainBalance>;
 	type Runner = Runner<Self>;
+	type StorageDepositCurrency = Ring;
+	type StorageDepositPerByte = StorageDepositPerByte;
 }
@@ -1,9 +1,10 @@
 // --- darwinia-network ---
-use crate::*;
+use crate::{weights::darwinia_header_mmr::WeightInfo, *};
 use darwinia_header_mmr::Config;
 
 impl Config for Runtime {
-	type WeightInfo = ();
+	type LeafData = ();
+	type WeightInfo = WeightInfo<Self>;
 
 	const INDEXING_PREFIX: &'static [u8] = b"header-mmr-";
 }
@@ -1,6 +1,22 @@
 // --- paritytech ---
 use darwinia_message_gadget::Config;
+use sp_core::{H160, H256};
 // --- darwinia-network ---
 use crate::*;
 
-impl Config for Runtime {}
+frame_support::parameter_types! {
+	// bytes4(keccak256("commitment()"))
+	pub const CommitmentSelector: [u8; 4] = [19, 3, 164, 132];
+	pub const CommitmentGasLimit: u64 = 1_000_000_000_000;
+	pub CommitmentCaller: H160 = H160::default();
+	pub const ExpectedCodeHash: Option<H256> = None;
+}
+
+impl Config for Runtime {
+	type CommitmentCaller = CommitmentCaller;
+	type CommitmentGasLimit = CommitmentGasLimit;
+	type CommitmentSelector = CommitmentSelector;
+	type Event = Event;
+	type ExpectedCodeHash = ExpectedCodeHash;
+	type ManagerOrigin = RootOrMoreThanHalf<TechnicalCollective>;
+}
@@ -1,16 +1,26 @@
 // --- paritytech ---
 use frame_support::PalletId;
 // --- darwinia-network ---
-use crate::*;
+use crate::{weights::to_tron_backing::WeightInfo, *};
 use to_tron_backing::Config;
 
 frame_support::parameter_types! {
 	pub const TronBackingPalletId: PalletId = PalletId(*b"da/trobk");
+	pub const RingOutflowLimit: Balance = 1_000_000 * COIN;
+	pub const KtonOutflowLimit: Balance = 1_000 * COIN;
+	pub const OutflowPeriod: BlockNumber = DAYS;
 }
 
 impl Config for Runtime {
+	type ApproveOrigin = RootOrMoreThanHalf<CouncilCollective>;
+	type Authorities = EcdsaAuthority;
+	type Event = Event;
 	type KtonCurrency = Kton;
+	type KtonOutflowLimit = KtonOutflowLimit;
+	type OutflowPeriod = OutflowPeriod;
 	type PalletId = TronBackingPalletId;
+	type PauseOrigin = RootOrMoreThanHalf<TechnicalCollective>;
 	type RingCurrency = Ring;
-	type WeightInfo = ();
+	type RingOutflowLimit = RingOutflowLimit;
+	type WeightInfo = WeightInfo<Self>;
 }
@@ -0,0 +1,70 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for `darwinia_header_mmr`
+//!
+//! NOT YET GENERATED. These numbers are the conservative estimates of the pallet, they must be
+//! replaced by the output of the command below on the reference hardware, with the same `STEPS`
+//! and `REPEAT` as the other weights of this runtime.
+
+// Regenerate with:
+// target/release/drml
+// benchmark
+// --header
+// .maintain/lincense-header
+// --execution
+// wasm
+// --heap-pages
+// 4096
+// --steps
+// 3
+// --repeat
+// 3
+// --chain
+// pangolin-dev
+// --output
+// node/runtime/pangolin/src/weights/
+// --extrinsic
+// *
+// --pallet
+// darwinia_header_mmr
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `darwinia_header_mmr`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> darwinia_header_mmr::WeightInfo for WeightInfo<T> {
+	// Storage: System ParentHash (r:1 w:0)
+	// Storage: HeaderMmr LeafVersionSince (r:1 w:0)
+	// Storage: HeaderMmr MmrSize (r:2 w:1)
+	// Storage: HeaderMmr Peaks (r:p w:p+1)
+	// Storage: System Digest (r:1 w:1)
+	fn on_finalize(p: u32, ) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((3_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+}
@@ -1,3 +1,4 @@
+pub mod darwinia_header_mmr;
 pub mod frame_system;
 pub mod pallet_bridge_grandpa_bridge_pangoro_grandpa;
 pub mod pallet_bridge_grandpa_bridge_rococo_grandpa;
@@ -19,3 +20,4 @@ pub mod pallet_treasury_kton_treasury;
 pub mod pallet_treasury_treasury;
 pub mod pallet_utility;
 pub mod pallet_vesting;
+pub mod to_tron_backing;
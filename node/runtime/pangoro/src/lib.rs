@@ -97,7 +97,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: sp_runtime::create_runtime_str!("Pangoro"),
 	impl_name: sp_runtime::create_runtime_str!("Pangoro"),
 	authoring_version: 0,
-	spec_version: 3_00_00_0,
+	spec_version: 3_01_00_0,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 0,
@@ -138,8 +138,8 @@ frame_support::construct_runtime!(
 		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>} = 12,
 		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event} = 13,
 		Beefy: pallet_beefy::{Pallet, Storage, Config<T>} = 27,
-		MessageGadget: darwinia_message_gadget::{Pallet, Call, Storage, Config} = 30,
-		EcdsaAuthority: darwinia_ecdsa_authority::{Pallet, Call, Storage, Config, Event<T>} = 32,
+		MessageGadget: darwinia_message_gadget::{Pallet, Call, Storage, Config, Event<T>} = 30,
+		EcdsaAuthority: darwinia_ecdsa_authority::{Pallet, Call, Storage, Config, Event<T>, ValidateUnsigned} = 32,
 		// Mmr: pallet_mmr::{Pallet, Storage} = 28,
 		// MmrLeaf: pallet_beefy_mmr::{Pallet, Storage} = 29,
 		ImOnline: pallet_im_online::{Pallet, Call, Storage, Config<T>, Event<T>, ValidateUnsigned} = 14,
@@ -420,6 +420,50 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl darwinia_ecdsa_authority_rpc_runtime_api::EcdsaAuthorityApi<Block> for Runtime {
+		fn pending_authorities_change(
+		) -> Option<darwinia_ecdsa_authority_rpc_runtime_api::SigningProgress> {
+			EcdsaAuthority::pending_authorities_change()
+		}
+
+		fn pending_new_message_root(
+			feed: darwinia_ecdsa_authority_rpc_runtime_api::Feed,
+		) -> Option<darwinia_ecdsa_authority_rpc_runtime_api::SigningProgress> {
+			EcdsaAuthority::pending_new_message_root(feed)
+		}
+	}
+
+	impl darwinia_evm_rpc_runtime_api::EvmStorageApi<Block> for Runtime {
+		fn storage_count(address: H160) -> Option<u32> {
+			darwinia_evm::Pallet::<Runtime>::storage_count_of(&address)
+		}
+
+		fn storage_paged(address: H160, start_after: Option<H256>, limit: u32) -> Vec<(H256, H256)> {
+			darwinia_evm::Pallet::<Runtime>::account_storages_paged(&address, start_after, limit)
+		}
+	}
+
+	impl darwinia_header_mmr_rpc_runtime_api::HeaderMmrApi<Block, Hash> for Runtime {
+		fn gen_proof(
+			_block_number: darwinia_header_mmr_rpc_runtime_api::NodeIndex,
+			_mmr_size: darwinia_header_mmr_rpc_runtime_api::NodeIndex,
+		) -> Option<darwinia_header_mmr_rpc_runtime_api::MmrProof<Hash>> {
+			// Pangoro doesn't maintain the header MMR.
+			None
+		}
+
+		fn gen_batch_proof(
+			_block_numbers: Vec<darwinia_header_mmr_rpc_runtime_api::NodeIndex>,
+			_mmr_size: darwinia_header_mmr_rpc_runtime_api::NodeIndex,
+		) -> Option<darwinia_header_mmr_rpc_runtime_api::MmrBatchProof<Hash>> {
+			None
+		}
+
+		fn leaf_node(_parent_hash: Hash) -> Option<Hash> {
+			None
+		}
+	}
+
 	impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 		fn chain_id() -> u64 {
 			<Runtime as darwinia_evm::Config>::ChainId::get()
@@ -22,6 +22,7 @@ use darwinia_evm::{
 };
 use darwinia_evm_precompile_bls12_381::BLS12381;
 use darwinia_evm_precompile_dispatch::Dispatch;
+use darwinia_evm_precompile_ecdsa_authority::EcdsaAuthority;
 use darwinia_evm_precompile_kton::{Erc20Metadata, KtonERC20};
 use darwinia_evm_precompile_state_storage::{StateStorage, StorageFilterT};
 use darwinia_support::evm::ConcatConverter;
@@ -57,7 +58,7 @@ where
 		Self(Default::default())
 	}
 
-	pub fn used_addresses() -> [H160; 13] {
+	pub fn used_addresses() -> [H160; 14] {
 		[
 			addr(1),
 			addr(2),
@@ -72,6 +73,7 @@ where
 			addr(1025),
 			addr(1026),
 			addr(2048),
+			addr(2049),
 		]
 	}
 }
@@ -95,6 +97,7 @@ impl<R> PrecompileSet for PangoroPrecompiles<R>
 where
 	BLS12381<R>: Precompile,
 	Dispatch<R>: Precompile,
+	EcdsaAuthority<R>: Precompile,
 	KtonERC20<R, KtonERC20MetaData>: Precompile,
 	R: darwinia_ethereum::Config,
 	StateStorage<R, StorageFilter>: Precompile,
@@ -139,6 +142,8 @@ where
 			// Darwinia precompiles: 2048+ for experimental precompiles.
 			a if a == addr(2048) =>
 				Some(<BLS12381<R>>::execute(input, target_gas, context, is_static)),
+			a if a == addr(2049) =>
+				Some(<EcdsaAuthority<R>>::execute(input, target_gas, context, is_static)),
 			_ => None,
 		}
 	}
@@ -148,13 +153,6 @@ where
 	}
 }
 
-pub struct FixedGasPrice;
-impl FeeCalculator for FixedGasPrice {
-	fn min_gas_price() -> U256 {
-		U256::from(GWEI)
-	}
-}
-
 pub struct FixedGasWeightMapping;
 impl GasWeightMapping for FixedGasWeightMapping {
 	fn gas_to_weight(gas: u64) -> Weight {
@@ -170,6 +168,7 @@ frame_support::parameter_types! {
 	pub const ChainId: u64 = 45;
 	pub BlockGasLimit: U256 = U256::from(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT / WEIGHT_PER_GAS);
 	pub PrecompilesValue: PangoroPrecompiles<Runtime> = PangoroPrecompiles::<_>::new();
+	pub const StorageDepositPerByte: Balance = 100 * MICRO;
 }
 
 impl Config for Runtime {
@@ -177,8 +176,10 @@ impl Config for Runtime {
 	type BlockHashMapping = EthereumBlockHashMapping<Self>;
 	type CallOrigin = EnsureAddressTruncated<Self::AccountId>;
 	type ChainId = ChainId;
+	type CreateOrigin = ();
+	type DeployerAllowlistOrigin = Root;
 	type Event = Event;
-	type FeeCalculator = FixedGasPrice;
+	type FeeCalculator = BaseFee;
 	type FindAuthor = EthereumFindAuthor<Babe>;
 	type GasWeightMapping = FixedGasWeightMapping;
 	type IntoAccountId = ConcatConverter<Self::AccountId>;
@@ -188,6 +189,8 @@ impl Config for Runtime {
 	type PrecompilesValue = PrecompilesValue;
 	type RingBalanceAdapter = CurrencyAdapter<Self, Ring, RingRemainBalance>;
 	type Runner = Runner<Self>;
+	type StorageDepositCurrency = Ring;
+	type StorageDepositPerByte = StorageDepositPerByte;
 }
 
 fn addr(a: u64) -> H160 {
@@ -91,6 +91,8 @@ impl Config for Runtime {
 	type BlockHashMapping = darwinia_ethereum::EthereumBlockHashMapping<Self>;
 	type CallOrigin = EnsureAddressTruncated<Self::AccountId>;
 	type ChainId = ChainId;
+	type CreateOrigin = ();
+	type DeployerAllowlistOrigin = RootOrigin;
 	type Event = Event;
 	type FeeCalculator = BaseFee;
 	type FindAuthor = FindAuthorTruncated<Aura>;
@@ -102,4 +104,6 @@ impl Config for Runtime {
 	type PrecompilesValue = PrecompilesValue;
 	type RingBalanceAdapter = CurrencyAdapter<Self, Ring, RingRemainBalance>;
 	type Runner = Runner<Self>;
+	type StorageDepositCurrency = Ring;
+	type StorageDepositPerByte = ();
 }
@@ -34,4 +34,6 @@ impl_runtime_apis![
 	fp_rpc::EthereumRuntimeRPCApi<Block>,
 	fp_rpc::ConvertTransactionRuntimeApi<Block>,
 	moonbeam_rpc_primitives_debug::DebugRuntimeApi<Block>,
+	darwinia_ecdsa_authority_rpc_runtime_api::EcdsaAuthorityApi<Block>,
+	darwinia_header_mmr_rpc_runtime_api::HeaderMmrApi<Block, Hash>,
 ];
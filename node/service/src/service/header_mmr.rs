@@ -0,0 +1,584 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+//! Maintain the offchain header MMR node store.
+//!
+//! The header MMR pallet writes the MMR nodes through the offchain indexing, keyed by
+//! `(INDEXING_PREFIX, position)`. This worker follows the finalized blocks to:
+//! - rebuild the nodes from the chain history, for the node which was started without the offchain
+//!   indexing
+//! - prune the nodes of the leaves deeper than the configured depth, except the peaks which are
+//!   still required by the proofs of the newer leaves
+
+// --- std ---
+use std::sync::Arc;
+// --- crates.io ---
+use codec::{Decode, Encode};
+// --- github.com ---
+use mmr::{Error as MmrError, MMRStore, Merge, MMR};
+// --- paritytech ---
+use sc_client_api::{Backend, BlockchainEvents};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Hash as HashT, Header},
+};
+use substrate_prometheus_endpoint::{register, Gauge, PrometheusError, Registry, U64};
+// --- darwinia-network ---
+use darwinia_header_mmr_rpc_runtime_api::HeaderMmrApi;
+use drml_primitives::{OpaqueBlock as Block, *};
+
+const LOG_TARGET: &str = "header-mmr";
+/// Log the rebuilding progress every such blocks.
+const PROGRESS_INTERVAL: BlockNumber = 1_000;
+
+/// The configuration of the offchain header MMR node store maintenance.
+#[derive(Clone, Debug, Default)]
+pub struct HeaderMmrConfig {
+	/// Rebuild the offchain MMR nodes from the chain history.
+	pub rebuild: bool,
+	/// Prune the offchain MMR nodes of the leaves which are deeper than this.
+	pub pruning_depth: Option<BlockNumber>,
+}
+impl HeaderMmrConfig {
+	pub fn is_enabled(&self) -> bool {
+		self.rebuild || self.pruning_depth.is_some()
+	}
+}
+
+pub struct HeaderMmrTaskParams<'a, C, BE> {
+	pub task_manager: &'a sc_service::TaskManager,
+	pub client: Arc<C>,
+	pub backend: Arc<BE>,
+	pub prometheus_registry: Option<Registry>,
+	/// The `INDEXING_PREFIX` of the runtime's header MMR pallet.
+	pub indexing_prefix: &'static [u8],
+	pub config: HeaderMmrConfig,
+}
+impl<'a, C, BE> HeaderMmrTaskParams<'a, C, BE>
+where
+	C: 'static
+		+ Send
+		+ Sync
+		+ HeaderBackend<Block>
+		+ BlockchainEvents<Block>
+		+ ProvideRuntimeApi<Block>,
+	C::Api: HeaderMmrApi<Block, Hash>,
+	BE: 'static + Backend<Block>,
+{
+	pub fn spawn_task(self) {
+		// --- crates.io ---
+		use futures::StreamExt;
+
+		let HeaderMmrTaskParams {
+			task_manager,
+			client,
+			backend,
+			prometheus_registry,
+			indexing_prefix,
+			config,
+		} = self;
+		let storage = if let Some(storage) = backend.offchain_storage() {
+			storage
+		} else {
+			log::warn!(
+				target: LOG_TARGET,
+				"Offchain storage is unavailable, skip the header MMR worker"
+			);
+
+			return;
+		};
+		let metrics = if let Some(registry) = prometheus_registry.as_ref() {
+			match Metrics::register(registry) {
+				Ok(metrics) => Some(metrics),
+				Err(e) => {
+					log::warn!(target: LOG_TARGET, "Failed to register the metrics due to {}", e);
+
+					None
+				},
+			}
+		} else {
+			None
+		};
+		let mut worker =
+			Worker { chain: client.clone(), storage, indexing_prefix, config, metrics };
+
+		task_manager.spawn_handle().spawn_blocking(
+			"header-mmr-worker",
+			Some("header-mmr"),
+			async move {
+				let mut finality_notifications = client.finality_notification_stream();
+
+				worker.on_finalized(client.info().finalized_number);
+
+				while let Some(notification) = finality_notifications.next().await {
+					worker.on_finalized(*notification.header.number());
+				}
+			},
+		);
+	}
+}
+
+struct Metrics {
+	rebuilt_block: Gauge<U64>,
+	pruned_leaves: Gauge<U64>,
+}
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			rebuilt_block: register(
+				Gauge::new(
+					"header_mmr_rebuilt_block",
+					"The latest block whose header MMR leaf is rebuilt in the offchain storage",
+				)?,
+				registry,
+			)?,
+			pruned_leaves: register(
+				Gauge::new(
+					"header_mmr_pruned_leaves",
+					"The number of header MMR leaves whose nodes are pruned from the offchain storage",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// The chain history which the MMR is rebuilt from.
+trait Chain {
+	/// The MMR node of the leaf which is pushed on block `block_number`.
+	fn leaf_node(&self, block_number: BlockNumber) -> Result<Hash, String>;
+
+	/// The parent MMR root in the digest of block `block_number`.
+	fn parent_mmr_root(&self, block_number: BlockNumber) -> Result<Option<Hash>, String>;
+}
+impl<C> Chain for C
+where
+	C: HeaderBackend<Block> + ProvideRuntimeApi<Block>,
+	C::Api: HeaderMmrApi<Block, Hash>,
+{
+	fn leaf_node(&self, block_number: BlockNumber) -> Result<Hash, String> {
+		let parent_hash = self
+			.hash(block_number - 1)
+			.map_err(|e| e.to_string())?
+			.ok_or_else(|| format!("Block #{} not found", block_number - 1))?;
+		let at = BlockId::Number(block_number);
+		let api = self.runtime_api();
+
+		// The runtime before the leaf data provider pushes the parent hash, the `V0` leaf.
+		if !api.has_api::<dyn HeaderMmrApi<Block, Hash>>(&at).map_err(|e| e.to_string())? {
+			return Ok(parent_hash);
+		}
+
+		api.leaf_node(&at, parent_hash)
+			.map_err(|e| e.to_string())?
+			.ok_or_else(|| format!("Header MMR is not maintained at block #{}", block_number))
+	}
+
+	fn parent_mmr_root(&self, block_number: BlockNumber) -> Result<Option<Hash>, String> {
+		Ok(self
+			.header(BlockId::Number(block_number))
+			.map_err(|e| e.to_string())?
+			.and_then(|header| dp_header_mmr::find_parent_mmr_root(&header)))
+	}
+}
+
+struct MmrHasher;
+impl Merge for MmrHasher {
+	type Item = Hash;
+
+	fn merge(lhs: &Self::Item, rhs: &Self::Item) -> Self::Item {
+		Hashing::hash_of(&(lhs, rhs))
+	}
+}
+
+struct NodeStore<S> {
+	storage: S,
+	indexing_prefix: &'static [u8],
+}
+impl<S> MMRStore<Hash> for NodeStore<S>
+where
+	S: OffchainStorage,
+{
+	fn get_elem(&self, position: u64) -> mmr::Result<Option<Hash>> {
+		Ok(self
+			.storage
+			.get(STORAGE_PREFIX, &node_key(self.indexing_prefix, position))
+			.and_then(|v| Decode::decode(&mut &*v).ok()))
+	}
+
+	fn append(&mut self, position: u64, elems: Vec<Hash>) -> mmr::Result<()> {
+		for (i, elem) in elems.into_iter().enumerate() {
+			self.storage.set(
+				STORAGE_PREFIX,
+				&node_key(self.indexing_prefix, position + i as u64),
+				&elem.encode(),
+			);
+		}
+
+		Ok(())
+	}
+}
+
+struct Worker<C, S> {
+	chain: Arc<C>,
+	storage: S,
+	indexing_prefix: &'static [u8],
+	config: HeaderMmrConfig,
+	metrics: Option<Metrics>,
+}
+impl<C, S> Worker<C, S>
+where
+	C: Chain,
+	S: OffchainStorage,
+{
+	fn on_finalized(&mut self, finalized: BlockNumber) {
+		if self.config.rebuild {
+			if let Err(e) = self.rebuild(finalized) {
+				log::error!(target: LOG_TARGET, "Stop rebuilding the header MMR due to {}", e);
+
+				self.config.rebuild = false;
+			}
+		}
+		if let Some(pruning_depth) = self.config.pruning_depth {
+			self.prune(finalized.saturating_sub(pruning_depth));
+		}
+	}
+
+	/// Push the leaves of the blocks till `finalized` into the MMR.
+	///
+	/// The leaf of block `n` is built from the hash of block `n - 1` by the runtime of block `n`,
+	/// and the MMR root after pushing it is in the digest of block `n`.
+	fn rebuild(&mut self, finalized: BlockNumber) -> Result<(), String> {
+		let rebuilt_to = self.get_cursor("rebuilt-to");
+
+		if rebuilt_to >= finalized {
+			return Ok(());
+		}
+
+		log::info!(
+			target: LOG_TARGET,
+			"Rebuilding the header MMR from block #{} to #{}",
+			rebuilt_to + 1,
+			finalized
+		);
+
+		for block_number in rebuilt_to + 1..=finalized {
+			let leaf = self.chain.leaf_node(block_number)?;
+			let mut mmr = MMR::<Hash, MmrHasher, _>::new(
+				mmr_size_of(block_number as u64 - 1),
+				NodeStore { storage: self.storage.clone(), indexing_prefix: self.indexing_prefix },
+			);
+
+			mmr.push(leaf).map_err(display)?;
+
+			let root = mmr.get_root().map_err(display)?;
+			let expected_root = self.chain.parent_mmr_root(block_number)?;
+
+			if expected_root != Some(root) {
+				return Err(format!(
+					"MMR root mismatch at block #{}, expected {:?}, got {:?}",
+					block_number, expected_root, root
+				));
+			}
+
+			mmr.commit().map_err(display)?;
+			self.set_cursor("rebuilt-to", block_number);
+
+			if let Some(metrics) = &self.metrics {
+				metrics.rebuilt_block.set(block_number as _);
+			}
+			if block_number % PROGRESS_INTERVAL == 0 {
+				log::info!(target: LOG_TARGET, "Rebuilt the header MMR to block #{}", block_number);
+			}
+		}
+
+		log::info!(target: LOG_TARGET, "Rebuilt the header MMR to block #{}", finalized);
+
+		Ok(())
+	}
+
+	/// Prune the nodes of the leaves before `boundary`.
+	///
+	/// The peaks of the MMR of these leaves are kept, they are the siblings on the paths of the
+	/// newer leaves.
+	fn prune(&mut self, boundary: BlockNumber) {
+		let pruned_to = self.get_cursor("pruned-to");
+
+		if boundary <= pruned_to {
+			return;
+		}
+
+		let peaks_of = |mmr_size| {
+			if mmr_size == 0 {
+				Vec::new()
+			} else {
+				mmr::helper::get_peaks(mmr_size)
+			}
+		};
+		let previous_mmr_size = mmr_size_of(pruned_to as _);
+		let mmr_size = mmr_size_of(boundary as _);
+		let peaks = peaks_of(mmr_size);
+		let mut pruned = 0;
+
+		// The previous peaks might be merged into the higher nodes now.
+		for position in peaks_of(previous_mmr_size).into_iter().chain(previous_mmr_size..mmr_size) {
+			if !peaks.contains(&position) {
+				self.storage.remove(STORAGE_PREFIX, &node_key(self.indexing_prefix, position));
+
+				pruned += 1;
+			}
+		}
+
+		self.set_cursor("pruned-to", boundary);
+
+		if let Some(metrics) = &self.metrics {
+			metrics.pruned_leaves.set(boundary as _);
+		}
+
+		log::info!(
+			target: LOG_TARGET,
+			"Pruned {} header MMR nodes of the leaves before #{}",
+			pruned,
+			boundary
+		);
+	}
+
+	fn get_cursor(&self, name: &str) -> BlockNumber {
+		self.storage
+			.get(STORAGE_PREFIX, &cursor_key(self.indexing_prefix, name))
+			.and_then(|v| Decode::decode(&mut &*v).ok())
+			.unwrap_or_default()
+	}
+
+	fn set_cursor(&mut self, name: &str, block_number: BlockNumber) {
+		self.storage.set(
+			STORAGE_PREFIX,
+			&cursor_key(self.indexing_prefix, name),
+			&block_number.encode(),
+		);
+	}
+}
+
+/// Same as the `darwinia_header_mmr::Pallet::offchain_key`.
+fn node_key(indexing_prefix: &[u8], position: u64) -> Vec<u8> {
+	(indexing_prefix, position).encode()
+}
+
+fn cursor_key(indexing_prefix: &[u8], name: &str) -> Vec<u8> {
+	(indexing_prefix, name).encode()
+}
+
+/// The MMR size of `leaf_count` leaves.
+fn mmr_size_of(leaf_count: u64) -> u64 {
+	if leaf_count == 0 {
+		0
+	} else {
+		mmr::leaf_index_to_mmr_size(leaf_count - 1)
+	}
+}
+
+fn display(e: MmrError) -> String {
+	e.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	// --- std ---
+	use std::sync::Mutex;
+	// --- paritytech ---
+	use sp_core::offchain::storage::InMemOffchainStorage;
+	// --- darwinia-network ---
+	use super::*;
+
+	const INDEXING_PREFIX: &[u8] = b"header-mmr-";
+
+	/// Share the nodes between the clones, like the offchain database does.
+	#[derive(Clone, Default)]
+	struct SharedStorage(Arc<Mutex<InMemOffchainStorage>>);
+	impl OffchainStorage for SharedStorage {
+		fn set(&mut self, prefix: &[u8], key: &[u8], value: &[u8]) {
+			self.0.lock().unwrap().set(prefix, key, value)
+		}
+
+		fn remove(&mut self, prefix: &[u8], key: &[u8]) {
+			self.0.lock().unwrap().remove(prefix, key)
+		}
+
+		fn get(&self, prefix: &[u8], key: &[u8]) -> Option<Vec<u8>> {
+			self.0.lock().unwrap().get(prefix, key)
+		}
+
+		fn compare_and_set(
+			&mut self,
+			prefix: &[u8],
+			key: &[u8],
+			old_value: Option<&[u8]>,
+			new_value: &[u8],
+		) -> bool {
+			self.0.lock().unwrap().compare_and_set(prefix, key, old_value, new_value)
+		}
+	}
+
+	/// The leaf node pushed on block `n` is `leaves[n - 1]`, and the parent MMR root in the digest
+	/// of block `n` is `roots[n - 1]`.
+	struct MockChain {
+		leaves: Vec<Hash>,
+		roots: Vec<Hash>,
+	}
+	impl MockChain {
+		fn new(n: BlockNumber) -> Self {
+			let leaves = (0..n).map(|i| Hashing::hash_of(&i)).collect::<Vec<_>>();
+			let storage = SharedStorage::default();
+			let roots = leaves
+				.iter()
+				.enumerate()
+				.map(|(i, leaf)| {
+					let mut mmr = MMR::<Hash, MmrHasher, _>::new(
+						mmr_size_of(i as _),
+						NodeStore { storage: storage.clone(), indexing_prefix: b"" },
+					);
+
+					mmr.push(*leaf).unwrap();
+
+					let root = mmr.get_root().unwrap();
+
+					mmr.commit().unwrap();
+
+					root
+				})
+				.collect();
+
+			Self { leaves, roots }
+		}
+	}
+	impl Chain for MockChain {
+		fn leaf_node(&self, block_number: BlockNumber) -> Result<Hash, String> {
+			self.leaves
+				.get(block_number as usize - 1)
+				.cloned()
+				.ok_or_else(|| format!("Block #{} not found", block_number))
+		}
+
+		fn parent_mmr_root(&self, block_number: BlockNumber) -> Result<Option<Hash>, String> {
+			Ok(self.roots.get(block_number as usize - 1).cloned())
+		}
+	}
+
+	fn worker(chain: MockChain, config: HeaderMmrConfig) -> Worker<MockChain, SharedStorage> {
+		Worker {
+			chain: Arc::new(chain),
+			storage: Default::default(),
+			indexing_prefix: INDEXING_PREFIX,
+			config,
+			metrics: None,
+		}
+	}
+
+	fn node_of(worker: &Worker<MockChain, SharedStorage>, position: u64) -> Option<Hash> {
+		NodeStore { storage: worker.storage.clone(), indexing_prefix: INDEXING_PREFIX }
+			.get_elem(position)
+			.unwrap()
+	}
+
+	/// Generate and verify the proof of `leaf_index` under the MMR of `leaf_count` leaves.
+	fn prove(
+		worker: &Worker<MockChain, SharedStorage>,
+		leaf_index: u64,
+		leaf_count: u64,
+	) -> mmr::Result<bool> {
+		let position = mmr::leaf_index_to_pos(leaf_index);
+		let proof = MMR::<Hash, MmrHasher, _>::new(
+			mmr_size_of(leaf_count),
+			NodeStore { storage: worker.storage.clone(), indexing_prefix: INDEXING_PREFIX },
+		)
+		.gen_proof(vec![position])?;
+
+		proof.verify(
+			worker.chain.roots[leaf_count as usize - 1],
+			vec![(position, worker.chain.leaves[leaf_index as usize])],
+		)
+	}
+
+	#[test]
+	fn rebuild_should_work() {
+		let mut worker =
+			worker(MockChain::new(10), HeaderMmrConfig { rebuild: true, pruning_depth: None });
+
+		worker.on_finalized(4);
+
+		assert_eq!(worker.get_cursor("rebuilt-to"), 4);
+		assert!(node_of(&worker, mmr_size_of(4) - 1).is_some());
+		assert!(node_of(&worker, mmr_size_of(4)).is_none());
+
+		// Continue from the cursor.
+		worker.on_finalized(10);
+
+		assert_eq!(worker.get_cursor("rebuilt-to"), 10);
+		assert!((0..mmr_size_of(10)).all(|position| node_of(&worker, position).is_some()));
+		assert!((0..10).all(|leaf_index| matches!(prove(&worker, leaf_index, 10), Ok(true))));
+	}
+
+	#[test]
+	fn rebuild_should_stop_on_root_mismatch() {
+		let mut chain = MockChain::new(10);
+
+		chain.roots[5] = Default::default();
+
+		let mut worker = worker(chain, HeaderMmrConfig { rebuild: true, pruning_depth: None });
+
+		worker.on_finalized(10);
+
+		// Block #6 is rejected, nothing after it is pushed.
+		assert_eq!(worker.get_cursor("rebuilt-to"), 5);
+		assert!(!worker.config.rebuild);
+		assert!(node_of(&worker, mmr_size_of(5)).is_none());
+	}
+
+	#[test]
+	fn prune_should_keep_peaks() {
+		let mut worker =
+			worker(MockChain::new(12), HeaderMmrConfig { rebuild: true, pruning_depth: Some(3) });
+
+		worker.on_finalized(10);
+
+		let boundary = mmr_size_of(7);
+		let peaks = mmr::helper::get_peaks(boundary);
+
+		assert_eq!(worker.get_cursor("pruned-to"), 7);
+		assert!((0..boundary)
+			.all(|position| node_of(&worker, position).is_some() == peaks.contains(&position)));
+		// The leaves after the boundary are still provable, so is the last leaf before it, which is
+		// a peak. The others are pruned.
+		assert!((6..10).all(|leaf_index| matches!(prove(&worker, leaf_index, 10), Ok(true))));
+		assert!((0..6).all(|leaf_index| prove(&worker, leaf_index, 10).is_err()));
+
+		// The previous peaks are merged into the higher ones.
+		worker.on_finalized(12);
+
+		let boundary = mmr_size_of(9);
+		let peaks = mmr::helper::get_peaks(boundary);
+
+		assert_eq!(worker.get_cursor("pruned-to"), 9);
+		assert!((0..boundary)
+			.all(|position| node_of(&worker, position).is_some() == peaks.contains(&position)));
+		assert!((9..12).all(|leaf_index| matches!(prove(&worker, leaf_index, 12), Ok(true))));
+	}
+}
@@ -3,6 +3,8 @@ use api::*;
 
 pub mod dvm;
 
+pub mod header_mmr;
+
 pub mod pangolin;
 pub use pangolin::Executor as PangolinExecutor;
 
@@ -101,6 +103,7 @@ fn new_full<RuntimeApi, Executor>(
 	mut config: sc_service::Configuration,
 	authority_discovery_disabled: bool,
 	eth_rpc_config: drml_rpc::EthRpcConfig,
+	header_mmr: Option<(&'static [u8], header_mmr::HeaderMmrConfig)>,
 ) -> ServiceResult<(
 	sc_service::TaskManager,
 	Arc<FullClient<RuntimeApi, Executor>>,
@@ -139,6 +142,7 @@ where
 	// --- darwinia-network ---
 	use drml_rpc::*;
 	use dvm::DvmTaskParams;
+	use header_mmr::HeaderMmrTaskParams;
 
 	let role = config.role.clone();
 	let is_authority = role.is_authority();
@@ -219,6 +223,21 @@ where
 		sync_from: 0,
 	}
 	.spawn_task();
+
+	if let Some((indexing_prefix, config)) = header_mmr {
+		if config.is_enabled() {
+			HeaderMmrTaskParams {
+				task_manager: &task_manager,
+				client: client.clone(),
+				backend: backend.clone(),
+				prometheus_registry: prometheus_registry.clone(),
+				indexing_prefix,
+				config,
+			}
+			.spawn_task();
+		}
+	}
+
 	let subscription_task_executor = SubscriptionTaskExecutor::new(task_manager.spawn_handle());
 	let shared_voter_state = GrandpaSharedVoterState::empty();
 	let babe_config = babe_link.config().clone();
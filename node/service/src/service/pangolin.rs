@@ -48,6 +48,7 @@ pub fn new_full(
 	config: sc_service::Configuration,
 	authority_discovery_disabled: bool,
 	eth_rpc_config: drml_rpc::EthRpcConfig,
+	header_mmr_config: header_mmr::HeaderMmrConfig,
 ) -> ServiceResult<(
 	sc_service::TaskManager,
 	Arc<impl DrmlClient<Block, FullBackend, RuntimeApi>>,
@@ -57,6 +58,10 @@ pub fn new_full(
 		config,
 		authority_discovery_disabled,
 		eth_rpc_config,
+		Some((
+			<pangolin_runtime::Runtime as darwinia_header_mmr::Config>::INDEXING_PREFIX,
+			header_mmr_config,
+		)),
 	)?;
 
 	Ok((components, client, rpc_handlers))
@@ -0,0 +1,169 @@
+// This file is part of Darwinia.
+//
+// Copyright (C) 2018-2022 Darwinia Network
+// SPDX-License-Identifier: GPL-3.0
+//
+// Darwinia is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Darwinia is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Darwinia. If not, see <https://www.gnu.org/licenses/>.
+
+//! Primitives of the Darwinia header MMR.
+//!
+//! The header MMR pallet pushes the parent MMR root into the digest of each block header as a
+//! [`MerkleMountainRangeRootLog`]. This crate allows the off-chain relayers and the other runtimes
+//! to decode it without depending on the pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// --- crates.io ---
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::Serialize;
+// --- paritytech ---
+use sp_runtime::{
+	generic::{DigestItem, OpaqueDigestItemId},
+	traits::{Hash as HashT, Header},
+	RuntimeDebug,
+};
+
+/// The prefix of [`MerkleMountainRangeRootLog`]
+pub const LOG_PREFIX: [u8; 4] = *b"MMRR";
+
+#[cfg_attr(feature = "std", derive(Serialize))]
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct MerkleMountainRangeRootLog<Hash> {
+	/// Specific prefix to identify the mmr root log in the digest items with Other type.
+	pub prefix: [u8; 4],
+	/// The merkle mountain range root hash.
+	pub parent_mmr_root: Hash,
+}
+impl<Hash> MerkleMountainRangeRootLog<Hash>
+where
+	Hash: Encode,
+{
+	pub fn new(parent_mmr_root: Hash) -> Self {
+		Self { prefix: LOG_PREFIX, parent_mmr_root }
+	}
+
+	/// Convert into the digest item which would be deposited in the header.
+	pub fn digest_item(&self) -> DigestItem {
+		DigestItem::Other(self.encode())
+	}
+}
+
+/// The versioned leaf of the header MMR.
+///
+/// The `V0` leaf is the parent hash, which is pushed into the MMR directly. Since `V1`, the leaf
+/// commits to the leaf data, e.g. `(parent_hash, state_root, message_root)`, and the hash of the
+/// encoded `VersionedLeaf` is pushed into the MMR.
+#[cfg_attr(feature = "std", derive(Serialize))]
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum VersionedLeaf<Hash, LeafData> {
+	V0(Hash),
+	V1(LeafData),
+}
+impl<Hash, LeafData> VersionedLeaf<Hash, LeafData>
+where
+	Hash: Clone + Encode,
+	LeafData: Encode,
+{
+	pub fn version(&self) -> u8 {
+		match self {
+			Self::V0(_) => 0,
+			Self::V1(_) => 1,
+		}
+	}
+
+	/// The MMR node of the leaf.
+	pub fn node<H>(&self) -> Hash
+	where
+		H: HashT<Output = Hash>,
+	{
+		match self {
+			Self::V0(parent_hash) => parent_hash.clone(),
+			Self::V1(_) => H::hash_of(self),
+		}
+	}
+}
+
+/// Find the parent MMR root in the header digest.
+pub fn find_parent_mmr_root<H>(header: &H) -> Option<H::Hash>
+where
+	H: Header,
+{
+	let find_parent_mmr_root = |m: MerkleMountainRangeRootLog<_>| match m.prefix {
+		LOG_PREFIX => Some(m.parent_mmr_root),
+		_ => None,
+	};
+
+	// find the first other digest with the right prefix which converts to
+	// the right kind of mmr root log.
+	header
+		.digest()
+		.convert_first(|d| d.try_to(OpaqueDigestItemId::Other).and_then(find_parent_mmr_root))
+}
+
+#[cfg(test)]
+mod tests {
+	// --- paritytech ---
+	use sp_core::H256;
+	use sp_runtime::{
+		testing::{Digest, Header},
+		traits::BlakeTwo256,
+	};
+	// --- darwinia-network ---
+	use super::*;
+
+	fn header_with_logs(logs: Vec<DigestItem>) -> Header {
+		Header {
+			parent_hash: Default::default(),
+			number: 1,
+			state_root: Default::default(),
+			extrinsics_root: Default::default(),
+			digest: Digest { logs },
+		}
+	}
+
+	#[test]
+	fn find_parent_mmr_root_should_work() {
+		let parent_mmr_root = H256::repeat_byte(1);
+
+		assert_eq!(
+			find_parent_mmr_root(&header_with_logs(vec![
+				DigestItem::Other(b"irrelevant".to_vec()),
+				MerkleMountainRangeRootLog::new(parent_mmr_root).digest_item(),
+			])),
+			Some(parent_mmr_root)
+		);
+		assert_eq!(
+			find_parent_mmr_root(&header_with_logs(vec![DigestItem::Other(
+				MerkleMountainRangeRootLog { prefix: *b"ABCD", parent_mmr_root }.encode()
+			)])),
+			None
+		);
+		assert_eq!(find_parent_mmr_root(&header_with_logs(vec![])), None);
+	}
+
+	#[test]
+	fn versioned_leaf_node_should_work() {
+		let parent_hash = H256::repeat_byte(1);
+
+		assert_eq!(<VersionedLeaf<H256, ()>>::V0(parent_hash).node::<BlakeTwo256>(), parent_hash);
+
+		let leaf = <VersionedLeaf<H256, (H256, u32)>>::V1((parent_hash, 1));
+
+		// The version is the first byte of the encoding.
+		assert_eq!(leaf.encode(), [vec![1], (parent_hash, 1_u32).encode()].concat());
+		assert_eq!(leaf.node::<BlakeTwo256>(), BlakeTwo256::hash_of(&leaf));
+	}
+}